@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use file_chunker::FileChunker;
+
+/// The byte-at-a-time boundary search that `chunks` used before switching to memchr, kept here
+/// as a baseline.
+fn chunks_bytewise(data: &[u8], count: usize, delimiter: u8) -> Vec<&[u8]> {
+    let chunk_size = data.len().div_ceil(count);
+    let mut chunks = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let mut chunk_end = (offset + chunk_size).min(data.len());
+        while chunk_end < data.len() && data[chunk_end] != delimiter {
+            chunk_end += 1;
+        }
+        chunk_end = (chunk_end + 1).min(data.len());
+        chunks.push(&data[offset..chunk_end]);
+        offset = chunk_end;
+    }
+    chunks
+}
+
+/// Returns `len` bytes of records that are each `record_len` bytes long including the newline.
+fn records(len: usize, record_len: usize) -> Vec<u8> {
+    let mut data = vec![b'x'; len];
+    for i in (record_len - 1..len).step_by(record_len) {
+        data[i] = b'\n';
+    }
+    data
+}
+
+fn chunks(c: &mut Criterion) {
+    let count = 7;
+    let mut group = c.benchmark_group("chunks");
+    for len in [1 << 20, 16 << 20] {
+        // Dense lines, moderate records, and records far larger than a chunk, where each
+        // boundary search scans a long way past the nominal chunk end.
+        for record_len in [64, 4 << 10, 1 << 20] {
+            let chunker = FileChunker::from_reader(&records(len, record_len)[..]).unwrap();
+            assert_eq!(
+                chunker.chunks(count, Some('\n')).unwrap(),
+                chunks_bytewise(chunker.as_bytes(), count, b'\n')
+            );
+
+            let parameter = format!("{}MiB/record={}B", len >> 20, record_len);
+            group.throughput(Throughput::Bytes(len as u64));
+            group.bench_with_input(BenchmarkId::new("memchr", &parameter), &chunker, |b, c| {
+                b.iter(|| c.chunks(black_box(count), Some('\n')).unwrap())
+            });
+            group.bench_with_input(
+                BenchmarkId::new("bytewise", &parameter),
+                &chunker,
+                |b, c| b.iter(|| chunks_bytewise(c.as_bytes(), black_box(count), b'\n')),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, chunks);
+criterion_main!(benches);
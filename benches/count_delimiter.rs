@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use file_chunker::FileChunker;
+use std::fs::File;
+use std::io::Write;
+
+const LINE: &str = "Nov 23 06:26:40 ip-10-1-1-1 haproxy[20128]: 10.1.1.10:57305 [23/Nov/2019:06:26:40.781] public myapp/i-05fa49c0e7db8c328 0/0/0/78/78 206 913/458 - - ---- 9/9/6/0/0 0/0 \"GET / HTTP/1.1\"\n";
+
+fn count_delimiter(c: &mut Criterion) {
+    let mut file: File = tempfile::tempfile().unwrap();
+    for _ in 0..100_000 {
+        file.write_all(LINE.as_bytes()).unwrap();
+    }
+    file.flush().unwrap();
+    let chunker = FileChunker::new(&file).unwrap();
+
+    let mut group = c.benchmark_group("count_delimiter");
+    group.throughput(Throughput::Bytes(chunker.len() as u64));
+    group.bench_function("memchr", |b| {
+        b.iter(|| chunker.count_delimiter(black_box(b'\n')))
+    });
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            let delimiter = black_box(b'\n');
+            chunker
+                .as_bytes()
+                .iter()
+                .filter(|&&b| b == delimiter)
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, count_delimiter);
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+use crate::FileChunker;
+use futures::stream::{self, Stream};
+
+impl FileChunker {
+    /// Lazily divide the file into chunks like [`chunk_iter`](Self::chunk_iter), and return them
+    /// as a [`Stream`] for use with the `futures` combinators. Each boundary is only computed
+    /// when the stream is polled for the next chunk.
+    ///
+    /// The chunks borrow from the chunker, so the stream can't outlive it. Processing chunks with
+    /// bounded concurrency looks like:
+    ///
+    /// ```
+    /// # use file_chunker::FileChunker;
+    /// # use futures::StreamExt;
+    /// # use std::io::Write;
+    /// # let mut file = tempfile::tempfile().unwrap();
+    /// # file.write_all(b"a\nb\nc\nd\n").unwrap();
+    /// let chunker = FileChunker::new(&file).unwrap();
+    /// let lens: Vec<usize> = futures::executor::block_on(
+    ///     chunker
+    ///         .chunk_stream(4, Some('\n'))
+    ///         .map(|chunk| async move { chunk.len() })
+    ///         .buffered(2)
+    ///         .collect(),
+    /// );
+    /// assert_eq!(lens.iter().sum::<usize>(), 8);
+    /// ```
+    ///
+    /// A `count` of zero is treated as one, producing a single chunk.
+    ///
+    /// Requires the `futures` feature.
+    pub fn chunk_stream(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+    ) -> impl Stream<Item = &[u8]> + '_ {
+        stream::iter(self.chunk_iter(count, delimiter))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::FileChunker;
+    use futures::StreamExt;
+    use std::fs::File;
+    use std::io::Write;
+
+    #[test]
+    fn chunk_stream_matches_chunks() {
+        let log = "01\n23\n45\n67\n89\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let streamed: Vec<&[u8]> =
+            futures::executor::block_on(chunker.chunk_stream(3, Some('\n')).collect());
+        assert_eq!(streamed, chunker.chunks(3, Some('\n')).unwrap());
+        let streamed: Vec<&[u8]> =
+            futures::executor::block_on(chunker.chunk_stream(0, Some('\n')).collect());
+        assert_eq!(streamed, vec![log.as_bytes()]);
+    }
+
+    #[test]
+    fn chunk_stream_polls_one_chunk_at_a_time() {
+        let log = "01\n23\n45\n67\n89\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let mut stream = chunker.chunk_stream(5, Some('\n'));
+        assert_eq!(
+            futures::executor::block_on(stream.next()),
+            Some(&b"01\n23\n"[..])
+        );
+        assert_eq!(
+            futures::executor::block_on(stream.next()),
+            Some(&b"45\n67\n"[..])
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{FileChunker, Result};
+use std::future::Future;
+use tokio::task::JoinSet;
+
+impl FileChunker {
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and spawn the future returned
+    /// by `f` for each chunk onto the current tokio runtime. Completes once every future has
+    /// finished, and resumes the panic if any of them panicked.
+    ///
+    /// Spawned tasks must be `'static`, so they can't borrow from the chunker. Each chunk is
+    /// therefore copied into an owned `Vec<u8>` before it is passed to `f`, which means the whole
+    /// file is held in memory while the tasks run.
+    ///
+    /// Must be called from within a tokio runtime. Requires the `tokio` feature.
+    pub async fn for_each_async<F, Fut>(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+        f: F,
+    ) -> Result<()>
+    where
+        F: Fn(Vec<u8>) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut tasks = JoinSet::new();
+        for chunk in self.chunks(count, delimiter)? {
+            tasks.spawn(f(chunk.to_vec()));
+        }
+        while let Some(result) = tasks.join_next().await {
+            if let Err(err) = result {
+                if err.is_panic() {
+                    std::panic::resume_unwind(err.into_panic());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::FileChunker;
+    use std::fs::File;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn for_each_async_visits_every_chunk() {
+        let log = "01\n23\n45\n67\n89\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime
+            .block_on(chunker.for_each_async(5, Some('\n'), |chunk| {
+                let seen = Arc::clone(&seen);
+                async move {
+                    seen.lock().unwrap().push(chunk);
+                }
+            }))
+            .unwrap();
+
+        let mut seen = seen.lock().unwrap().clone();
+        seen.sort();
+        assert_eq!(seen.concat(), log.as_bytes());
+        assert_eq!(seen.len(), chunker.chunks(5, Some('\n')).unwrap().len());
+    }
+}
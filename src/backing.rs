@@ -0,0 +1,57 @@
+#[cfg(unix)]
+use memmap2::Advice;
+use memmap2::Mmap;
+use std::ops::Deref;
+
+/// The storage that a `FileChunker` reads from.
+pub(crate) enum Backing {
+    Mmap(Mmap),
+    Buffer(Vec<u8>),
+}
+
+impl Backing {
+    /// Forward an access pattern hint to the kernel. In-memory buffers ignore the hint.
+    #[cfg(unix)]
+    pub(crate) fn advise(&self, advice: Advice) -> std::io::Result<()> {
+        match self {
+            Backing::Mmap(mmap) => mmap.advise(advice),
+            Backing::Buffer(_) => Ok(()),
+        }
+    }
+
+    /// Lock the mapped pages into memory. In-memory buffers can't be locked.
+    pub(crate) fn lock(&mut self) -> std::io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Backing::Mmap(mmap) => mmap.lock(),
+            _ => Err(unsupported("memory locking")),
+        }
+    }
+
+    /// Unlock pages previously locked with `lock`.
+    pub(crate) fn unlock(&mut self) -> std::io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Backing::Mmap(mmap) => mmap.unlock(),
+            _ => Err(unsupported("memory locking")),
+        }
+    }
+}
+
+fn unsupported(operation: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("{} is not supported for this chunker", operation),
+    )
+}
+
+impl Deref for Backing {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Backing::Mmap(mmap) => mmap,
+            Backing::Buffer(buffer) => buffer,
+        }
+    }
+}
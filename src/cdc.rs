@@ -0,0 +1,194 @@
+use crate::{ChunkerError, FileChunker, Result};
+
+// One pseudo-random 64-bit value per byte value, fixed so that boundaries are reproducible
+// across runs and builds.
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    // SplitMix64, seeded with an arbitrary constant.
+    let mut table = [0; 256];
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut i = 0;
+    while i < table.len() {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+impl FileChunker {
+    /// Divide the file into content-defined chunks, whose boundaries depend on the bytes around
+    /// them rather than on their position in the file. Inserting or removing bytes therefore only
+    /// changes the chunks near the edit, and the rest stay identical, which is what backup and
+    /// deduplication systems need to recognize data they've already stored.
+    ///
+    /// A rolling Gear hash is computed over the bytes of each chunk, and a boundary is placed
+    /// where its top bits are all zero. The hash only depends on the last 64 bytes, so the same
+    /// content produces the same boundary wherever it appears.
+    ///
+    /// - `min` is the smallest chunk size. No boundary is considered in the first `min` bytes of
+    ///   a chunk, which also skips hashing them.
+    /// - `avg_size` is the expected chunk size. The number of bits tested is chosen so that a
+    ///   boundary is expected about `avg_size - min` bytes after the minimum, rounded to a power
+    ///   of two.
+    /// - `max` is the largest chunk size. A chunk is cut at `max` bytes if no boundary was found,
+    ///   which bounds the chunk size for data such as long runs of a single byte.
+    ///
+    /// Only the last chunk may be smaller than `min`.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkSizeBounds`] unless `min <= avg_size <= max` and `max`
+    /// is greater than zero.
+    pub fn chunks_cdc(&self, avg_size: usize, min: usize, max: usize) -> Result<Vec<&[u8]>> {
+        if max == 0 || min > avg_size || avg_size > max {
+            return Err(ChunkerError::InvalidChunkSizeBounds {
+                min,
+                avg: avg_size,
+                max,
+            });
+        }
+
+        let bits = (avg_size - min)
+            .max(1)
+            .checked_next_power_of_two()
+            .map_or(usize::BITS, |p| p.trailing_zeros());
+        let mask = match bits {
+            0 => 0,
+            bits => u64::MAX << (64 - bits.min(63)),
+        };
+        let data = self.as_bytes();
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            let limit = offset + (data.len() - offset).min(max);
+            let mut chunk_end = limit;
+            let mut hash = 0u64;
+            for (i, &b) in data
+                .iter()
+                .enumerate()
+                .take(limit)
+                .skip(offset.saturating_add(min))
+            {
+                hash = (hash << 1).wrapping_add(GEAR[b as usize]);
+                if hash & mask == 0 {
+                    chunk_end = i + 1;
+                    break;
+                }
+            }
+            chunks.push(&data[offset..chunk_end]);
+            offset = chunk_end;
+        }
+
+        Ok(chunks)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ChunkerError, FileChunker};
+    use std::fs::File;
+    use std::io::Write;
+
+    fn pseudo_random_bytes(len: usize, mut state: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 56) as u8
+            })
+            .collect()
+    }
+
+    fn chunker_for(contents: &[u8]) -> FileChunker {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(contents).unwrap();
+        file.flush().unwrap();
+        FileChunker::new(&file).unwrap()
+    }
+
+    #[test]
+    fn chunks_cdc_respects_bounds() {
+        let data = pseudo_random_bytes(256 * 1024, 1);
+        let chunker = chunker_for(&data);
+        let chunks = chunker.chunks_cdc(4096, 1024, 16384).unwrap();
+        assert_eq!(chunks.concat(), data);
+        let (last, rest) = chunks.split_last().unwrap();
+        assert!(last.len() <= 16384);
+        assert!(rest
+            .iter()
+            .all(|chunk| (1024..=16384).contains(&chunk.len())));
+
+        // The sizes vary with the content, but average out near `avg_size`.
+        let mean = data.len() / chunks.len();
+        assert!((2048..8192).contains(&mean), "mean chunk size {}", mean);
+
+        // Chunks without a boundary within the maximum are cut there.
+        let chunks = chunker.chunks_cdc(4096, 0, 4096).unwrap();
+        assert_eq!(chunks.concat(), data);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 4096));
+        assert!(chunks.iter().any(|chunk| chunk.len() == 4096));
+    }
+
+    #[test]
+    fn chunks_cdc_is_shift_resistant() {
+        let data = pseudo_random_bytes(256 * 1024, 2);
+        let mut edited = data.clone();
+        edited.splice(1000..1000, b"inserted bytes".iter().copied());
+
+        let original = chunker_for(&data);
+        let original = original.chunks_cdc(4096, 1024, 16384).unwrap();
+        let edited = chunker_for(&edited);
+        let edited = edited.chunks_cdc(4096, 1024, 16384).unwrap();
+
+        // Only the chunks around the insertion differ.
+        let shared = edited
+            .iter()
+            .filter(|chunk| original.contains(chunk))
+            .count();
+        assert!(
+            shared + 2 >= original.len(),
+            "{} of {}",
+            shared,
+            original.len()
+        );
+    }
+
+    #[test]
+    fn chunks_cdc_invalid_sizes() {
+        let chunker = chunker_for(b"01\n23\n45\n67\n89");
+        for (avg, min, max) in [(4, 8, 16), (32, 8, 16), (0, 0, 0)] {
+            assert!(matches!(
+                chunker.chunks_cdc(avg, min, max),
+                Err(ChunkerError::InvalidChunkSizeBounds { .. })
+            ));
+        }
+        assert_eq!(
+            chunker.chunks_cdc(4, 4, 4).unwrap().concat(),
+            chunker.as_bytes()
+        );
+    }
+
+    #[test]
+    fn chunks_cdc_extreme_sizes() {
+        let data = pseudo_random_bytes(64 * 1024, 3);
+        let chunker = chunker_for(&data);
+        for (avg, min, max) in [
+            (usize::MAX, 0, usize::MAX),
+            (usize::MAX, usize::MAX, usize::MAX),
+            (usize::MAX / 2 + 2, 0, usize::MAX),
+        ] {
+            assert_eq!(
+                chunker.chunks_cdc(avg, min, max).unwrap(),
+                vec![&data[..]],
+                "avg {} min {} max {}",
+                avg,
+                min,
+                max
+            );
+        }
+    }
+}
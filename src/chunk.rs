@@ -0,0 +1,96 @@
+use crate::Result;
+use std::ops::Deref;
+
+/// A chunk of a file along with details about how it was produced.
+///
+/// This is returned by [`FileChunker::chunks_detailed`](crate::FileChunker::chunks_detailed). It
+/// dereferences to the chunk's bytes, so it can be used wherever a `&[u8]` is expected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Chunk<'a> {
+    /// The bytes of the chunk.
+    pub bytes: &'a [u8],
+    /// The byte offset of the start of the chunk in the file.
+    pub offset: usize,
+    /// Whether the chunk ends with the delimiter. This is `false` for a final chunk that runs to
+    /// the end of the file without a trailing delimiter, which may hold a truncated record, and
+    /// for every chunk when no delimiter was given.
+    pub ends_on_delimiter: bool,
+}
+
+impl<'a> Chunk<'a> {
+    /// Returns the bytes of the chunk, borrowed for as long as the chunker.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the chunk as a string slice.
+    ///
+    /// Returns [`ChunkerError::Utf8`](crate::ChunkerError::Utf8) if the chunk is not valid UTF-8.
+    pub fn as_str(&self) -> Result<&'a str> {
+        Ok(std::str::from_utf8(self.bytes)?)
+    }
+
+    /// Returns an iterator over the lines of the chunk, without their line terminators. As with
+    /// [`str::lines`], lines end with `\n` or `\r\n`, and a final line terminator doesn't start
+    /// an empty line.
+    pub fn lines(&self) -> impl Iterator<Item = &'a [u8]> {
+        self.bytes
+            .split_inclusive(|&b| b == b'\n')
+            .map(|line| match line.strip_suffix(b"\n") {
+                Some(line) => line.strip_suffix(b"\r").unwrap_or(line),
+                None => line,
+            })
+    }
+}
+
+impl Deref for Chunk<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ChunkerError, FileChunker};
+    use std::fs::File;
+    use std::io::Write;
+
+    #[test]
+    fn chunk_helpers() {
+        let log = "01\r\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_detailed(2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].as_str().unwrap(), "01\r\n23\n45\n");
+        assert_eq!(chunks[0].len(), 10);
+        assert_eq!(chunks[1].offset, 10);
+        assert!(chunks[1].starts_with(b"67"));
+        assert_eq!(
+            chunks[0].lines().collect::<Vec<_>>(),
+            vec![&b"01"[..], b"23", b"45"]
+        );
+        assert_eq!(
+            chunks[1].lines().collect::<Vec<_>>(),
+            vec![&b"67"[..], b"89"]
+        );
+    }
+
+    #[test]
+    fn chunk_as_str_invalid() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(b"01\n\xff\n").unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_detailed(5, Some('\n')).unwrap();
+        assert!(chunks[0].as_str().is_ok());
+        assert!(matches!(chunks[1].as_str(), Err(ChunkerError::Utf8(_))));
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::str::Utf8Error;
+
+/// A specialized `Result` type for chunking operations.
+pub type Result<T> = std::result::Result<T, ChunkerError>;
+
+/// The error type for chunking operations.
+#[derive(Debug)]
+pub enum ChunkerError {
+    /// An I/O error occurred while opening or mapping the file.
+    Io(io::Error),
+    /// The file is empty.
+    EmptyFile,
+    /// The requested number of chunks was zero.
+    InvalidChunkCount,
+    /// The requested chunk size was zero.
+    InvalidChunkSize,
+    /// The delimiter was empty.
+    EmptyDelimiter,
+    /// The file is not valid UTF-8.
+    Utf8(Utf8Error),
+    /// The requested byte range is inverted or extends past the end of the file.
+    InvalidRange(Range<usize>),
+    /// Fewer chunks could be produced than were requested, typically because the delimiter is
+    /// absent or too sparse.
+    InsufficientChunks { requested: usize, achieved: usize },
+    /// The file is larger than the platform's address space, so it cannot be mapped.
+    FileTooLarge { size: u64 },
+    /// The file length is not a whole multiple of the record size.
+    UnalignedLength { len: usize, record_size: usize },
+    /// The operation was cancelled before it finished.
+    Cancelled,
+    /// The chunks are not adjacent slices of the chunker's data.
+    NonContiguousChunks,
+    /// The minimum, average and maximum chunk sizes are out of order, or the maximum is zero.
+    InvalidChunkSizeBounds { min: usize, avg: usize, max: usize },
+}
+
+impl fmt::Display for ChunkerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChunkerError::Io(err) => write!(f, "I/O error: {}", err),
+            ChunkerError::EmptyFile => write!(f, "file is empty"),
+            ChunkerError::InvalidChunkCount => write!(f, "chunk count must be greater than zero"),
+            ChunkerError::InvalidChunkSize => write!(f, "chunk size must be greater than zero"),
+            ChunkerError::EmptyDelimiter => write!(f, "delimiter must not be empty"),
+            ChunkerError::Utf8(err) => write!(f, "invalid UTF-8: {}", err),
+            ChunkerError::InvalidRange(range) => {
+                write!(f, "invalid byte range {}..{}", range.start, range.end)
+            }
+            ChunkerError::InsufficientChunks {
+                requested,
+                achieved,
+            } => write!(
+                f,
+                "requested {} chunks, but only {} could be produced",
+                requested, achieved
+            ),
+            ChunkerError::FileTooLarge { size } => write!(
+                f,
+                "file of {} bytes exceeds the addressable range of this platform",
+                size
+            ),
+            ChunkerError::UnalignedLength { len, record_size } => write!(
+                f,
+                "file length {} is not a multiple of the record size {}",
+                len, record_size
+            ),
+            ChunkerError::Cancelled => write!(f, "operation was cancelled"),
+            ChunkerError::NonContiguousChunks => {
+                write!(f, "chunks are not adjacent slices of the file")
+            }
+            ChunkerError::InvalidChunkSizeBounds { min, avg, max } => write!(
+                f,
+                "chunk sizes must satisfy 0 < max and min <= avg <= max, got min {}, avg {}, max {}",
+                min, avg, max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChunkerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ChunkerError::Io(err) => Some(err),
+            ChunkerError::Utf8(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ChunkerError {
+    fn from(err: io::Error) -> Self {
+        ChunkerError::Io(err)
+    }
+}
+
+impl From<Utf8Error> for ChunkerError {
+    fn from(err: Utf8Error) -> Self {
+        ChunkerError::Utf8(err)
+    }
+}
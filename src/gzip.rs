@@ -0,0 +1,55 @@
+use crate::backing::Backing;
+use crate::{FileChunker, Result};
+use flate2::read::MultiGzDecoder;
+use std::fs::File;
+use std::io::Read;
+
+impl FileChunker {
+    /// Create a new FileChunker by decompressing a gzip-compressed file into memory. The
+    /// chunking methods then operate on the decompressed data.
+    ///
+    /// Requires the `flate2` feature.
+    pub fn from_gzip(file: &File) -> Result<Self> {
+        let mut buffer = Vec::new();
+        MultiGzDecoder::new(file).read_to_end(&mut buffer)?;
+        Ok(Self::from_backing(Backing::Buffer(buffer), None))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ChunkerError, FileChunker};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::fs::File;
+    use std::io::{Seek, Write};
+
+    #[test]
+    fn from_gzip() {
+        let log = "01\n23\n45\n67\n89";
+
+        let file: File = tempfile::tempfile().unwrap();
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        encoder.write_all(log.as_bytes()).unwrap();
+        let mut file = encoder.finish().unwrap();
+        file.rewind().unwrap();
+
+        let chunker = FileChunker::from_gzip(&file).unwrap();
+        let chunks = chunker.chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n45\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89");
+    }
+
+    #[test]
+    fn from_gzip_invalid() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(b"not gzip").unwrap();
+        file.rewind().unwrap();
+
+        assert!(matches!(
+            FileChunker::from_gzip(&file),
+            Err(ChunkerError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,341 @@
+use crate::chunk_size;
+use std::cell::RefCell;
+use std::ops::Range;
+
+/// Where a chunk boundary falls relative to the delimiter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Boundary {
+    /// Each chunk ends with a delimiter, so the delimiter stays with the preceding record.
+    #[default]
+    TrailingDelimiter,
+    /// Each chunk after the first begins with a delimiter. This suits formats where a marker
+    /// precedes each record.
+    LeadingDelimiter,
+}
+
+/// Compute the `start..end` byte ranges that divide `data` into `count` chunks of approximately
+/// equal size, with each chunk ending on `delimiter` if one is given. This is the same algorithm
+/// that [`FileChunker::chunks`](crate::FileChunker::chunks) uses, but it works on any byte slice
+/// and needs nothing beyond `core` and `alloc`, so it can be lifted into a `no_std` setting.
+///
+/// The ranges are contiguous and together cover `data`. A `count` of zero is treated as one.
+pub fn chunk_boundaries(data: &[u8], count: usize, delimiter: Option<u8>) -> Vec<Range<usize>> {
+    let chunk_size = chunk_size(data.len(), count).max(1);
+    ChunkIter::new(data, chunk_size, delimiter)
+        .into_ranges()
+        .collect()
+}
+
+/// A lazy, indexable view of the chunks of a file. Boundaries are computed only as chunks are
+/// requested, so looking up a few chunks of a file divided into very many doesn't allocate the
+/// whole list.
+///
+/// Without a delimiter, every chunk is found in constant time. With one, the boundaries are
+/// found in order and remembered, so looking up chunk `i` for the first time scans as far as
+/// the end of that chunk, and looking it up again is constant time. Because of that cache, a
+/// `Chunks` can't be shared between threads; use [`chunk_boundaries`] for that instead.
+///
+/// This is created by [`FileChunker::chunks_ref`](crate::FileChunker::chunks_ref).
+pub struct Chunks<'a> {
+    data: &'a [u8],
+    chunk_size: usize,
+    delimiter: Option<u8>,
+    // The end offsets of the chunks found so far, when there is a delimiter.
+    ends: RefCell<Vec<usize>>,
+}
+
+impl<'a> Chunks<'a> {
+    pub(crate) fn new(data: &'a [u8], count: usize, delimiter: Option<u8>) -> Self {
+        Self {
+            data,
+            chunk_size: chunk_size(data.len(), count).max(1),
+            delimiter,
+            ends: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the chunk at `index`, or `None` if there are fewer chunks than that.
+    pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+        self.range(index).map(|range| &self.data[range])
+    }
+
+    /// Returns the number of chunks. With a delimiter, this finds every boundary.
+    pub fn len(&self) -> usize {
+        match self.delimiter {
+            None => self.data.len().div_ceil(self.chunk_size),
+            Some(_) => {
+                self.range(usize::MAX);
+                self.ends.borrow().len()
+            }
+        }
+    }
+
+    /// Returns `true` if there are no chunks, which is only the case for empty data.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn range(&self, index: usize) -> Option<Range<usize>> {
+        if self.delimiter.is_none() {
+            let start = index.checked_mul(self.chunk_size)?;
+            if start >= self.data.len() {
+                return None;
+            }
+            return Some(start..start.saturating_add(self.chunk_size).min(self.data.len()));
+        }
+
+        let mut ends = self.ends.borrow_mut();
+        while ends.len() <= index {
+            let start = ends.last().copied().unwrap_or(0);
+            let next = ChunkIter::new(self.data, self.chunk_size, self.delimiter)
+                .with_offset(start)
+                .next_range()?;
+            ends.push(next.end);
+        }
+        let start = index.checked_sub(1).map_or(0, |i| ends[i]);
+        Some(start..ends[index])
+    }
+}
+
+impl<'a> IntoIterator for Chunks<'a> {
+    type Item = &'a [u8];
+    type IntoIter = ChunkIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ChunkIter::new(self.data, self.chunk_size, self.delimiter)
+    }
+}
+
+impl<'a> IntoIterator for &Chunks<'a> {
+    type Item = &'a [u8];
+    type IntoIter = ChunkIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ChunkIter::new(self.data, self.chunk_size, self.delimiter)
+    }
+}
+
+/// A lazy iterator over the chunks of a file. Each boundary is computed on demand, so no
+/// intermediate vector of chunks is allocated.
+///
+/// This is created by [`FileChunker::chunk_iter`](crate::FileChunker::chunk_iter).
+pub struct ChunkIter<'a> {
+    data: &'a [u8],
+    chunk_size: usize,
+    delimiter: Option<u8>,
+    boundary: Boundary,
+    alignment: usize,
+    // The number of remaining chunks that get one byte more than `chunk_size`.
+    extra_bytes: usize,
+    offset: usize,
+}
+
+impl<'a> ChunkIter<'a> {
+    pub(crate) fn new(data: &'a [u8], chunk_size: usize, delimiter: Option<u8>) -> Self {
+        debug_assert!(chunk_size > 0);
+        Self {
+            data,
+            chunk_size,
+            delimiter,
+            boundary: Boundary::TrailingDelimiter,
+            alignment: 1,
+            extra_bytes: 0,
+            offset: 0,
+        }
+    }
+
+    pub(crate) fn with_boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    pub(crate) fn with_alignment(mut self, alignment: usize) -> Self {
+        self.alignment = alignment.max(1);
+        self
+    }
+
+    pub(crate) fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub(crate) fn with_extra_bytes(mut self, extra_bytes: usize) -> Self {
+        self.extra_bytes = extra_bytes;
+        self
+    }
+
+    pub(crate) fn next_range(&mut self) -> Option<Range<usize>> {
+        if self.offset >= self.data.len() {
+            return None;
+        }
+
+        let start = self.offset;
+        let extra_byte = usize::from(self.extra_bytes > 0);
+        self.extra_bytes -= extra_byte;
+        let mut chunk_end = start
+            .saturating_add(self.chunk_size + extra_byte)
+            .div_ceil(self.alignment)
+            .saturating_mul(self.alignment)
+            .min(self.data.len());
+        if let Some(delimiter) = self.delimiter {
+            let next_delimiter = memchr::memchr(delimiter, &self.data[chunk_end..])
+                .map(|position| chunk_end + position);
+            chunk_end = match (self.boundary, next_delimiter) {
+                (Boundary::TrailingDelimiter, Some(position)) => position + 1,
+                (Boundary::LeadingDelimiter, Some(position)) => position,
+                (_, None) => self.data.len(),
+            };
+        }
+        self.offset = chunk_end;
+
+        Some(start..chunk_end)
+    }
+
+    pub(crate) fn into_ranges(mut self) -> impl Iterator<Item = Range<usize>> + 'a {
+        std::iter::from_fn(move || self.next_range())
+    }
+}
+
+impl<'a> Iterator for ChunkIter<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        self.next_range().map(|range| &data[range])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{chunk_boundaries, Chunks};
+    use crate::FileChunker;
+    use std::fs::File;
+    use std::io::Write;
+
+    #[test]
+    fn chunks_ref_matches_chunks() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for count in 1..=log.len() {
+            for delimiter in [None, Some('\n')] {
+                let expected = chunker.chunks(count, delimiter).unwrap();
+
+                // Look up chunks out of order, so later boundaries are found before earlier ones
+                // are requested.
+                let view = chunker.chunks_ref(count, delimiter);
+                for i in (0..=expected.len()).rev() {
+                    assert_eq!(view.get(i), expected.get(i).copied());
+                }
+                assert_eq!(view.len(), expected.len());
+                assert_eq!((&view).into_iter().collect::<Vec<_>>(), expected);
+
+                let view = chunker.chunks_ref(count, delimiter);
+                assert_eq!(view.len(), expected.len());
+                assert_eq!(view.into_iter().collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_ref_empty() {
+        let view = Chunks::new(b"", 4, Some(b'\n'));
+        assert!(view.is_empty());
+        assert_eq!(view.len(), 0);
+        assert_eq!(view.get(0), None);
+    }
+
+    #[test]
+    fn chunk_iter_matches_chunks() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for count in 1..=log.len() {
+            for delimiter in [None, Some('\n')] {
+                let chunks = chunker.chunks(count, delimiter).unwrap();
+                let iterated: Vec<_> = chunker.chunk_iter(count, delimiter).collect();
+                assert_eq!(iterated, chunks);
+            }
+        }
+    }
+
+    #[test]
+    fn chunk_iter_delimiter_at_last_byte() {
+        let log = "0123456789\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for chunk_size in [log.len() - 1, log.len(), log.len() + 1] {
+            let chunks = chunker.chunks_by_size(chunk_size, Some('\n')).unwrap();
+            assert_eq!(chunks, vec![log.as_bytes()]);
+        }
+        let chunks = chunker.chunks_by_size(5, Some('\n')).unwrap();
+        assert_eq!(chunks, vec![log.as_bytes()]);
+        let chunks = chunker.chunks(1, Some('\n')).unwrap();
+        assert_eq!(chunks, vec![log.as_bytes()]);
+    }
+
+    #[test]
+    fn chunk_iter_single_byte() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(b"\n").unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for count in 1..=3 {
+            let chunks = chunker.chunks(count, Some('\n')).unwrap();
+            assert_eq!(chunks, vec![&b"\n"[..]]);
+        }
+    }
+
+    #[test]
+    fn chunk_iter_is_lazy() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let mut iter = chunker.chunk_iter(3, None);
+        assert_eq!(iter.next(), Some(&b"0123"[..]));
+        assert_eq!(iter.next(), Some(&b"4567"[..]));
+        assert_eq!(iter.next(), Some(&b"89"[..]));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn chunk_boundaries_match_boundaries() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for count in 1..=log.len() {
+            for delimiter in [None, Some('\n')] {
+                assert_eq!(
+                    chunk_boundaries(log.as_bytes(), count, delimiter.map(|d| d as u8)),
+                    chunker.boundaries(count, delimiter).unwrap()
+                );
+            }
+        }
+        assert_eq!(
+            chunk_boundaries(log.as_bytes(), 0, None),
+            vec![0..log.len()]
+        );
+        assert!(chunk_boundaries(b"", 2, Some(b'\n')).is_empty());
+    }
+}
@@ -9,8 +9,7 @@
 //! ```rust,no_run
 //! use file_chunker::FileChunker;
 //!
-//! let file = std::fs::File::open("/path/to/file").unwrap();
-//! let chunker = FileChunker::new(&file).unwrap();
+//! let chunker = FileChunker::open("/path/to/file").unwrap();
 //! chunker.chunks(1024, Some('\n'))
 //!     .unwrap()
 //!     .iter()
@@ -19,67 +18,3391 @@
 //!     });
 //! ```
 //!
+//! ## Sharing chunks between threads
+//!
+//! `FileChunker` is `Send` and `Sync`, so borrowed chunks can be handed to scoped threads
+//! without copying:
+//!
+//! ```rust,no_run
+//! use file_chunker::FileChunker;
+//!
+//! let chunker = FileChunker::open("/path/to/file").unwrap();
+//! let chunks = chunker.chunks(4, Some('\n')).unwrap();
+//! std::thread::scope(|scope| {
+//!     for chunk in chunks {
+//!         scope.spawn(move || println!("{} bytes", chunk.len()));
+//!     }
+//! });
+//! ```
+//!
+//! Threads that must be `'static`, such as those from `std::thread::spawn`, can share the
+//! chunker through an `Arc` and receive byte ranges from `FileChunker::boundaries` instead of
+//! slices:
+//!
+//! ```rust,no_run
+//! use file_chunker::FileChunker;
+//! use std::sync::Arc;
+//!
+//! let chunker = Arc::new(FileChunker::open("/path/to/file").unwrap());
+//! let handles: Vec<_> = chunker
+//!     .boundaries(4, Some('\n'))
+//!     .unwrap()
+//!     .into_iter()
+//!     .map(|range| {
+//!         let chunker = Arc::clone(&chunker);
+//!         std::thread::spawn(move || chunker.as_bytes()[range].len())
+//!     })
+//!     .collect();
+//! ```
+//!
+//! ## Features
+//!
+//! - `flate2`: Adds `FileChunker::from_gzip` for chunking gzip-compressed files.
+//! - `futures`: Adds `FileChunker::chunk_stream` for processing chunks as a `Stream`.
+//! - `rayon`: Adds `FileChunker::par_for_each` for processing chunks in parallel.
+//! - `regex`: Adds `FileChunker::chunks_regex` for placing boundaries at regex matches.
+//! - `tokio`: Adds `FileChunker::for_each_async` for processing chunks as tokio tasks.
+//! - `zstd`: Adds `FileChunker::from_zstd` for chunking zstd-compressed files.
+//!
 
-use anyhow::Result;
-use memmap2::Mmap;
-use std::fs::File;
+#[cfg(feature = "futures")]
+mod async_futures;
+#[cfg(feature = "tokio")]
+mod async_tokio;
+mod backing;
+mod cdc;
+mod chunk;
+mod error;
+#[cfg(feature = "flate2")]
+mod gzip;
+mod iter;
+mod mutable;
+mod options;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "regex")]
+mod pattern;
+mod slice;
+mod stats;
+mod stream;
+#[cfg(feature = "zstd")]
+mod zst;
+
+pub use chunk::Chunk;
+pub use error::{ChunkerError, Result};
+pub use iter::{chunk_boundaries, Boundary, ChunkIter, Chunks};
+pub use mutable::FileChunkerMut;
+pub use options::{ChunkDistribution, ChunkerOptions, OnInsufficientRecords};
+pub use slice::SliceChunker;
+pub use stats::ChunkStats;
+pub use stream::stream_chunks;
+
+use backing::Backing;
+use memmap2::{Mmap, MmapOptions};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs::{self, File};
+use std::hash::Hasher;
+use std::io::{self, Read};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
 
 pub struct FileChunker {
-    mmap: Mmap,
+    data: Backing,
+    len: usize,
+    // Keeps the file open for as long as the mapping exists when the chunker opened it.
+    _file: Option<File>,
+}
+
+/// A source of data that can be divided into chunks. This allows code to be generic over how the
+/// data is stored, such as a memory-mapped file or an in-memory buffer.
+pub trait Chunker {
+    /// Returns all of the bytes to divide into chunks.
+    fn as_bytes(&self) -> &[u8];
+
+    /// Divide the data into chunks of approximately equal size. See [`FileChunker::chunks`].
+    fn chunks(&self, count: usize, delimiter: Option<char>) -> Result<Vec<&[u8]>> {
+        split(self.as_bytes(), count, delimiter.map(|d| d as u8))
+    }
+}
+
+/// The byte order of a UTF-16 code unit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endian {
+    /// Least significant byte first, as in UTF-16LE.
+    Little,
+    /// Most significant byte first, as in UTF-16BE.
+    Big,
+}
+
+impl Chunker for FileChunker {
+    fn as_bytes(&self) -> &[u8] {
+        FileChunker::as_bytes(self)
+    }
+}
+
+impl fmt::Debug for FileChunker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The contents can be huge, so only the length is shown.
+        f.debug_struct("FileChunker")
+            .field("len", &self.len)
+            .finish()
+    }
 }
 
 impl FileChunker {
     /// Create a new FileChunker
+    ///
+    /// Returns [`ChunkerError::FileTooLarge`] if the file does not fit in the address space, which
+    /// can happen on 32-bit targets.
     pub fn new(file: &File) -> Result<Self> {
+        check_addressable(file)?;
+        let mmap = unsafe { Mmap::map(file)? };
+        Ok(Self::from_backing(Backing::Mmap(mmap), None))
+    }
+
+    /// Create a new FileChunker like [`new`](Self::new), but retry mapping the file up to
+    /// `retries` times if it fails with a transient error, such as an interrupted system call on
+    /// a network filesystem. The delay between attempts starts at 1ms and doubles each time.
+    ///
+    /// Errors that retrying can't fix, such as a permission error, are returned immediately.
+    pub fn new_with_retries(file: &File, retries: usize) -> Result<Self> {
+        check_addressable(file)?;
+        let mmap = with_retries(retries, || unsafe { Mmap::map(file) })?;
+        Ok(Self::from_backing(Backing::Mmap(mmap), None))
+    }
+
+    /// Create a new FileChunker like [`new`](Self::new), but fault the whole mapping into memory
+    /// up front, so that reading the chunks later doesn't stall on page faults. This moves the
+    /// I/O cost to construction, which makes the first pass over the data more predictable.
+    ///
+    /// This uses `MAP_POPULATE`, which is only supported on Linux. On other platforms it behaves
+    /// exactly like `new`, and pages are faulted in as they're first read;
+    /// [`advise_willneed`](Self::advise_willneed) is a weaker alternative there.
+    pub fn new_populated(file: &File) -> Result<Self> {
+        check_addressable(file)?;
+        let mmap = unsafe { MmapOptions::new().populate().map(file)? };
+        Ok(Self::from_backing(Backing::Mmap(mmap), None))
+    }
+
+    /// Create a new FileChunker by mapping an already-open file descriptor, such as one passed
+    /// from another process. The descriptor is only borrowed: the chunker doesn't close it, and
+    /// the caller may close it as soon as this returns, since the mapping stays valid on its own.
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be an open file descriptor for the duration of this call.
+    #[cfg(unix)]
+    pub unsafe fn from_raw_fd(fd: std::os::unix::io::RawFd) -> Result<Self> {
+        use std::os::unix::io::FromRawFd;
+
+        // Wrapping the descriptor in `ManuallyDrop` keeps `File` from closing it.
+        let file = std::mem::ManuallyDrop::new(File::from_raw_fd(fd));
+        Self::new(&file)
+    }
+
+    /// Create a new FileChunker by mapping an already-open file handle, such as one passed from
+    /// another process. The handle is only borrowed: the chunker doesn't close it, and the
+    /// caller may close it as soon as this returns, since the mapping stays valid on its own.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be an open file handle for the duration of this call.
+    #[cfg(windows)]
+    pub unsafe fn from_raw_handle(handle: std::os::windows::io::RawHandle) -> Result<Self> {
+        use std::os::windows::io::FromRawHandle;
+
+        // Wrapping the handle in `ManuallyDrop` keeps `File` from closing it.
+        let file = std::mem::ManuallyDrop::new(File::from_raw_handle(handle));
+        Self::new(&file)
+    }
+
+    /// Create a new FileChunker like [`new`](Self::new), but reject an empty file up front with
+    /// [`ChunkerError::EmptyFile`] instead of producing a chunker with no chunks.
+    pub fn try_new(file: &File) -> Result<Self> {
+        let chunker = Self::new(file)?;
+        if chunker.is_empty() {
+            return Err(ChunkerError::EmptyFile);
+        }
+        Ok(chunker)
+    }
+
+    /// Open the file at `path` and create a new FileChunker that owns it.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path)?;
+        check_addressable(&file)?;
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(Self::from_backing(Backing::Mmap(mmap), Some(file)))
+    }
+
+    /// Create a new FileChunker by reading the whole file into memory instead of mapping it.
+    /// This works for files that cannot be memory-mapped, such as pipes, special files, and
+    /// files on some networked filesystems.
+    pub fn new_buffered(mut file: &File) -> Result<Self> {
+        check_addressable(file)?;
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+        Ok(Self::from_backing(Backing::Buffer(buffer), None))
+    }
+
+    /// Create a new FileChunker by reading `reader` to the end into memory. This makes it
+    /// possible to chunk data that can't be memory-mapped at all, such as standard input in a
+    /// pipeline.
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        Ok(Self::from_backing(Backing::Buffer(buffer), None))
+    }
+
+    /// Replace the chunker's data with a fresh memory map of `file`, for example after the file
+    /// has grown. The length is reset to the new size of the file, discarding any limit set by
+    /// [`with_len`](Self::with_len), and any memory lock on the old mapping is released.
+    ///
+    /// Chunks borrowed from the old mapping must be dropped first, which the borrow checker
+    /// enforces.
+    pub fn remap(&mut self, file: &File) -> Result<()> {
+        check_addressable(file)?;
         let mmap = unsafe { Mmap::map(file)? };
-        Ok(Self { mmap })
+        self.data = Backing::Mmap(mmap);
+        self.len = self.data.len();
+        Ok(())
+    }
+
+    fn from_backing(data: Backing, file: Option<File>) -> Self {
+        Self {
+            len: data.len(),
+            data,
+            _file: file,
+        }
+    }
+
+    /// Advise the kernel that the file will be read sequentially, which typically enables more
+    /// aggressive readahead. Call this after creating the chunker and before reading the chunks.
+    ///
+    /// This forwards `madvise(MADV_SEQUENTIAL)` to the mapping. It is a no-op on platforms that
+    /// don't support `madvise` and for chunkers that aren't backed by a memory map.
+    pub fn advise_sequential(&self) -> Result<()> {
+        #[cfg(unix)]
+        self.data.advise(memmap2::Advice::Sequential)?;
+        Ok(())
+    }
+
+    /// Advise the kernel that the whole file will be needed soon, so it can start reading it
+    /// into memory ahead of time.
+    ///
+    /// This forwards `madvise(MADV_WILLNEED)` to the mapping. It is a no-op on platforms that
+    /// don't support `madvise` and for chunkers that aren't backed by a memory map.
+    pub fn advise_willneed(&self) -> Result<()> {
+        #[cfg(unix)]
+        self.data.advise(memmap2::Advice::WillNeed)?;
+        Ok(())
+    }
+
+    /// Lock the mapped file into memory, so that reading chunks never waits for pages to be
+    /// read from disk. The pages stay locked until [`unlock`](Self::unlock) is called or the
+    /// chunker is dropped.
+    ///
+    /// This forwards to `mlock` and is only supported on Unix platforms for memory-mapped
+    /// chunkers; elsewhere it returns an [`std::io::ErrorKind::Unsupported`] error. The amount of
+    /// memory a process may lock is limited by `RLIMIT_MEMLOCK`, which is often small for
+    /// unprivileged processes, so locking a large file can fail.
+    pub fn lock(&mut self) -> Result<()> {
+        Ok(self.data.lock()?)
+    }
+
+    /// Unlock pages that were locked with [`lock`](Self::lock).
+    pub fn unlock(&mut self) -> Result<()> {
+        Ok(self.data.unlock()?)
+    }
+
+    /// Limit the chunker to the first `len` bytes of the file. Lengths beyond the size of the
+    /// file at the time it was mapped are clamped to that size.
+    ///
+    /// The chunker only ever reads bytes within the length recorded when it was created, so data
+    /// appended to the file later is ignored. If the file is truncated after it has been mapped,
+    /// reading the missing pages can still fault (`SIGBUS` on Unix). To observe a file that has
+    /// changed, create a new `FileChunker` for it.
+    pub fn with_len(mut self, len: usize) -> Self {
+        self.len = len.min(self.data.len());
+        self
+    }
+
+    /// Returns the number of bytes that the chunker divides into chunks.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no bytes to divide into chunks.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns all of the bytes that the chunker divides into chunks as a single slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+
+    /// Returns a builder for configuring how the file is divided into chunks. This is an
+    /// alternative to the positional-argument methods such as [`chunks`](Self::chunks).
+    pub fn options(&self) -> ChunkerOptions<'_> {
+        ChunkerOptions::new(self)
+    }
+
+    /// Returns the size of a memory page, which is the granularity of memory mappings. Used by
+    /// [`ChunkerOptions::page_aligned`].
+    ///
+    /// On platforms other than Unix, this assumes the common page size of 4 KiB.
+    pub fn page_size() -> usize {
+        #[cfg(unix)]
+        {
+            // sysconf only fails for unknown names, and _SC_PAGESIZE is always known.
+            let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+            usize::try_from(size).unwrap_or(4096)
+        }
+        #[cfg(not(unix))]
+        {
+            4096
+        }
+    }
+
+    /// Divide the file into chunks approximately equal size. Returns a vector of memory-mapped
+    /// slices that each correspond to a chunk.
+    ///
+    /// If a delimeter is provided, then each chunk will end with an instance of the delimeter,
+    /// assuming the delimiter exists in the file. This is useful when working with text files
+    /// that have newline characters, for example. If no delimeter is provided, then each chunk
+    /// will be the same size, except for the last chunk which may be smaller.
+    ///
+    /// The delimiter must be an ASCII character. It is converted to a single byte, so a
+    /// non-ASCII `char` is truncated and matches an unrelated byte. Use
+    /// [`chunks_u8`](Self::chunks_u8) for an arbitrary byte, or
+    /// [`chunks_by_delimiter`](Self::chunks_by_delimiter) for a multi-byte delimiter.
+    ///
+    /// An empty file produces no chunks, so the returned vector is empty. Use
+    /// [`ChunkerOptions::allow_empty_chunk`] to get a single empty chunk instead.
+    ///
+    /// It is assumed that the underlying `File` will not be truncated while the chunks are in use.
+    /// Data appended to the file after the chunker was created is ignored; see
+    /// [`with_len`](Self::with_len) and [`remap`](Self::remap).
+    ///
+    /// Calling this repeatedly, with the same or different arguments, always chunks the same
+    /// mapping. It never re-maps or re-reads the file, so re-chunking only costs the boundary
+    /// search and the returned `Vec`.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkCount`] if `count` is zero.
+    pub fn chunks(&self, count: usize, delimiter: Option<char>) -> Result<Vec<&[u8]>> {
+        split(self.as_bytes(), count, delimiter.map(|d| d as u8))
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), but allocate room for
+    /// `cap_hint` chunks up front. [`chunks`](Self::chunks) already reserves room for `count`
+    /// chunks, which is an upper bound; a tighter hint saves memory when delimiters are sparse and
+    /// far fewer chunks are expected.
+    pub fn chunks_with_capacity(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+        cap_hint: usize,
+    ) -> Result<Vec<&[u8]>> {
+        split_with_capacity(self.as_bytes(), count, delimiter.map(|d| d as u8), cap_hint)
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), but with the delimiter given
+    /// as a byte. This works for any byte value, including those that aren't ASCII characters.
+    pub fn chunks_u8(&self, count: usize, delimiter: Option<u8>) -> Result<Vec<&[u8]>> {
+        split(self.as_bytes(), count, delimiter)
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), but write them into `out`
+    /// instead of allocating a new vector. `out` is cleared first, and its capacity is reused.
+    pub fn chunks_into<'a>(
+        &'a self,
+        count: usize,
+        delimiter: Option<char>,
+        out: &mut Vec<&'a [u8]>,
+    ) -> Result<()> {
+        let chunk_size = self.chunk_size(count)?;
+        out.clear();
+        out.extend(ChunkIter::new(
+            self.as_bytes(),
+            chunk_size,
+            delimiter.map(|d| d as u8),
+        ));
+        Ok(())
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and pair each chunk with a
+    /// hash of its bytes. The hash algorithm is chosen by the `H` type parameter, which can be
+    /// any [`Hasher`] such as a CRC32 or xxHash implementation. A fresh hasher is created for
+    /// each chunk and fed exactly the bytes of the returned slice.
+    pub fn chunks_with_hashes<H: Hasher + Default>(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+    ) -> Result<Vec<(&[u8], u64)>> {
+        Ok(self
+            .chunks(count, delimiter)?
+            .into_iter()
+            .map(|chunk| {
+                let mut hasher = H::default();
+                hasher.write(chunk);
+                (chunk, hasher.finish())
+            })
+            .collect())
+    }
+
+    /// Divide a text file into chunks of approximately equal size, where each chunk ends with a
+    /// complete line terminator. Both `\n` and `\r\n` are recognized, and a `\r\n` pair is
+    /// never split between two chunks, so no chunk begins with a stray `\n`.
+    pub fn chunks_lines(&self, count: usize) -> Result<Vec<&[u8]>> {
+        // Cutting after '\n' keeps the preceding '\r' of a "\r\n" pair in the same chunk.
+        self.chunks(count, Some('\n'))
+    }
+
+    /// Divide a text file into chunks of approximately equal size, where each chunk ends with
+    /// `delimiter`, skipping a leading byte order mark. This suits text exported by Windows
+    /// tools, which often starts with a BOM.
+    ///
+    /// A UTF-8 BOM is skipped and the rest of the file is chunked like
+    /// [`chunks`](Self::chunks). After a UTF-16 BOM, the delimiter is matched as a 2-byte code
+    /// unit in the byte order the BOM indicates, and boundaries only fall between code units.
+    /// Without a BOM, this is the same as `chunks`. The delimiter must be an ASCII character.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkCount`] if `count` is zero.
+    pub fn chunks_text(&self, count: usize, delimiter: char) -> Result<Vec<&[u8]>> {
+        let data = self.as_bytes();
+        let code_unit = delimiter as u16;
+        if let Some(text) = data.strip_prefix(b"\xef\xbb\xbf") {
+            split(text, count, Some(delimiter as u8))
+        } else if let Some(text) = data.strip_prefix(b"\xff\xfe") {
+            split_utf16(text, count, Some(code_unit.to_le_bytes()))
+        } else if let Some(text) = data.strip_prefix(b"\xfe\xff") {
+            split_utf16(text, count, Some(code_unit.to_be_bytes()))
+        } else {
+            self.chunks(count, Some(delimiter))
+        }
+    }
+
+    /// Divide a UTF-16 text file into chunks of approximately equal size, where each chunk ends
+    /// with a newline code unit in the given byte order. Boundaries only fall between code
+    /// units, so a `0x0A` byte that belongs to another character is never mistaken for a newline.
+    ///
+    /// The file is chunked as-is, so a leading byte order mark stays in the first chunk; see
+    /// [`chunks_text`](Self::chunks_text) to detect the byte order from the BOM instead.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkCount`] if `count` is zero.
+    pub fn chunks_utf16(&self, count: usize, endian: Endian) -> Result<Vec<&[u8]>> {
+        let newline = match endian {
+            Endian::Little => 0x0au16.to_le_bytes(),
+            Endian::Big => 0x0au16.to_be_bytes(),
+        };
+        split_utf16(self.as_bytes(), count, Some(newline))
+    }
+
+    /// Divide the file into one chunk per available CPU, like [`chunks`](Self::chunks) with a
+    /// count of [`std::thread::available_parallelism`]. If the available parallelism can't be
+    /// determined, the whole file is returned as a single chunk.
+    pub fn chunks_per_cpu(&self, delimiter: Option<char>) -> Result<Vec<&[u8]>> {
+        self.chunks(cpu_count(), delimiter)
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), but merge the last chunk into
+    /// the one before it if it is smaller than `min_fraction` of the nominal chunk size. For
+    /// example, with a `min_fraction` of `0.5`, a trailing chunk less than half the size of a
+    /// full chunk is appended to the previous chunk instead of standing alone.
+    pub fn chunks_merged(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+        min_fraction: f64,
+    ) -> Result<Vec<&[u8]>> {
+        let chunk_size = self.chunk_size(count)?;
+        let mut boundaries = self.boundaries(count, delimiter)?;
+        if boundaries.len() >= 2 {
+            let last = &boundaries[boundaries.len() - 1];
+            if ((last.end - last.start) as f64) < min_fraction * chunk_size as f64 {
+                let last = boundaries.pop().unwrap();
+                boundaries.last_mut().unwrap().end = last.end;
+            }
+        }
+
+        let data = self.as_bytes();
+        Ok(boundaries.into_iter().map(|range| &data[range]).collect())
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), but return an error instead of
+    /// silently producing fewer chunks than requested. This catches mistakes such as passing the
+    /// wrong delimiter, which would otherwise collapse the file into a single chunk.
+    ///
+    /// Returns [`ChunkerError::InsufficientChunks`] with the number of chunks that could be
+    /// produced if it is less than `count`.
+    pub fn chunks_strict(&self, count: usize, delimiter: char) -> Result<Vec<&[u8]>> {
+        let chunks = self.chunks(count, Some(delimiter))?;
+        if chunks.len() < count {
+            return Err(ChunkerError::InsufficientChunks {
+                requested: count,
+                achieved: chunks.len(),
+            });
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), but exclude the trailing
+    /// delimiter from each chunk. The last chunk is returned unchanged if the file does not end
+    /// with the delimiter.
+    ///
+    /// The total length of the chunks is less than the file size by the number of delimiters
+    /// that were stripped.
+    pub fn chunks_trim_delimiter(&self, count: usize, delimiter: char) -> Result<Vec<&[u8]>> {
+        let suffix = [delimiter as u8];
+        Ok(self
+            .chunks(count, Some(delimiter))?
+            .into_iter()
+            .map(|chunk| chunk.strip_suffix(&suffix).unwrap_or(chunk))
+            .collect())
+    }
+
+    /// Divide a UTF-8 text file into chunks of approximately equal size, where each boundary is
+    /// moved forward to the next character boundary so that no character is split between
+    /// chunks.
+    ///
+    /// Returns [`ChunkerError::Utf8`] if the file is not valid UTF-8.
+    pub fn chunks_utf8(&self, count: usize) -> Result<Vec<&str>> {
+        let chunk_size = self.chunk_size(count)?;
+        let text = std::str::from_utf8(self.as_bytes())?;
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+        while offset < text.len() {
+            let mut chunk_end = offset.saturating_add(chunk_size).min(text.len());
+            while !text.is_char_boundary(chunk_end) {
+                chunk_end += 1;
+            }
+            chunks.push(&text[offset..chunk_end]);
+            offset = chunk_end;
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and copy each chunk into an
+    /// owned buffer. The chunks don't borrow the chunker, so they can be moved into threads or
+    /// tasks that require `'static` data.
+    ///
+    /// This copies the whole file into memory. Prefer [`chunks`](Self::chunks), which borrows
+    /// the mapped data without copying, whenever the borrow can be scoped, such as with
+    /// [`std::thread::scope`].
+    pub fn chunks_owned(&self, count: usize, delimiter: Option<char>) -> Result<Vec<Vec<u8>>> {
+        Ok(self
+            .chunks(count, delimiter)?
+            .into_iter()
+            .map(<[u8]>::to_vec)
+            .collect())
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and convert each chunk to a
+    /// string. A chunk that is valid UTF-8 is borrowed without copying. A chunk with invalid
+    /// UTF-8 is copied into an owned string, with each invalid sequence replaced by
+    /// `U+FFFD REPLACEMENT CHARACTER`, as in [`String::from_utf8_lossy`].
+    pub fn str_chunks(&self, count: usize, delimiter: Option<char>) -> Result<Vec<Cow<'_, str>>> {
+        Ok(self
+            .chunks(count, delimiter)?
+            .into_iter()
+            .map(String::from_utf8_lossy)
+            .collect())
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and report each chunk's offset
+    /// in the file and whether it ends on a delimiter. The latter distinguishes complete records
+    /// from a truncated record at the end of a file that doesn't end with the delimiter.
+    pub fn chunks_detailed(&self, count: usize, delimiter: Option<char>) -> Result<Vec<Chunk<'_>>> {
+        Ok(self
+            .boundaries(count, delimiter)?
+            .into_iter()
+            .map(|range| {
+                let bytes = &self.as_bytes()[range.clone()];
+                Chunk {
+                    bytes,
+                    offset: range.start,
+                    ends_on_delimiter: delimiter
+                        .is_some_and(|delimiter| bytes.last() == Some(&(delimiter as u8))),
+                }
+            })
+            .collect())
+    }
+
+    /// Compute summary statistics about the chunks that [`chunks`](Self::chunks) would produce,
+    /// without collecting them. This is useful for logging and for tuning `count`.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkCount`] if `count` is zero.
+    pub fn chunk_stats(&self, count: usize, delimiter: Option<char>) -> Result<ChunkStats> {
+        let chunk_size = self.chunk_size(count)?;
+        let mut stats = ChunkStats {
+            count: 0,
+            min_len: usize::MAX,
+            max_len: 0,
+            mean_len: 0.0,
+            last_ends_on_delimiter: false,
+        };
+        let mut last = None;
+        for chunk in ChunkIter::new(self.as_bytes(), chunk_size, delimiter.map(|d| d as u8)) {
+            stats.count += 1;
+            stats.min_len = stats.min_len.min(chunk.len());
+            stats.max_len = stats.max_len.max(chunk.len());
+            last = Some(chunk);
+        }
+        if stats.count == 0 {
+            stats.min_len = 0;
+        } else {
+            stats.mean_len = self.len as f64 / stats.count as f64;
+        }
+        stats.last_ends_on_delimiter =
+            matches!((delimiter, last), (Some(d), Some(chunk)) if chunk.last() == Some(&(d as u8)));
+
+        Ok(stats)
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), treating the end of the file
+    /// as an implicit delimiter so that every chunk, including the last, ends with `delimiter`.
+    /// This lets a parser treat each chunk as a sequence of complete records, whether or not the
+    /// file ends with a delimiter.
+    ///
+    /// Chunks are borrowed from the file, except that if the file doesn't end with the delimiter,
+    /// the last chunk is copied so the delimiter can be appended.
+    pub fn chunks_implicit_final(
+        &self,
+        count: usize,
+        delimiter: char,
+    ) -> Result<Vec<Cow<'_, [u8]>>> {
+        let delimiter_byte = delimiter as u8;
+        let mut chunks: Vec<Cow<'_, [u8]>> = self
+            .chunks(count, Some(delimiter))?
+            .into_iter()
+            .map(Cow::Borrowed)
+            .collect();
+        if let Some(last) = chunks.last_mut() {
+            if last.last() != Some(&delimiter_byte) {
+                last.to_mut().push(delimiter_byte);
+            }
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and split each chunk after its
+    /// first delimiter. Each pair holds the first record, including its delimiter, and the rest
+    /// of the chunk. This is useful when each chunk starts with a header or a partial record.
+    ///
+    /// If a chunk contains no delimiter, the whole chunk is the first record and the rest is
+    /// empty.
+    pub fn chunks_split_first(&self, count: usize, delimiter: char) -> Result<Vec<(&[u8], &[u8])>> {
+        let delimiter_byte = delimiter as u8;
+        Ok(self
+            .chunks(count, Some(delimiter))?
+            .into_iter()
+            .map(|chunk| match memchr::memchr(delimiter_byte, chunk) {
+                Some(position) => chunk.split_at(position + 1),
+                None => (chunk, &chunk[chunk.len()..]),
+            })
+            .collect())
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and pair each chunk with the
+    /// number of delimiter occurrences it contains. For newline-delimited text, this is the
+    /// number of lines in the chunk, not counting a trailing line without a newline.
+    pub fn chunks_with_counts(&self, count: usize, delimiter: char) -> Result<Vec<(&[u8], usize)>> {
+        let delimiter_byte = delimiter as u8;
+        Ok(self
+            .chunks(count, Some(delimiter))?
+            .into_iter()
+            .map(|chunk| {
+                let count = memchr::memchr_iter(delimiter_byte, chunk).count();
+                (chunk, count)
+            })
+            .collect())
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and pair each chunk with the
+    /// 1-based line number of its first line, counting `delimiter` as the line terminator. Adding
+    /// a line offset within a chunk to this number gives the line's number in the whole file.
+    pub fn chunks_with_line_numbers(
+        &self,
+        count: usize,
+        delimiter: char,
+    ) -> Result<Vec<(usize, &[u8])>> {
+        let delimiter_byte = delimiter as u8;
+        let mut line = 1;
+        Ok(self
+            .chunks(count, Some(delimiter))?
+            .into_iter()
+            .map(|chunk| {
+                let first_line = line;
+                line += memchr::memchr_iter(delimiter_byte, chunk).count();
+                (first_line, chunk)
+            })
+            .collect())
+    }
+
+    /// Divide the file into exactly `count` delimiter-aligned chunks, distributing the records
+    /// (such as lines) as evenly as possible between them. Unlike [`chunks`](Self::chunks),
+    /// which balances bytes and can produce fewer chunks than requested, this only returns fewer
+    /// than `count` chunks if the file has fewer than `count` records.
+    ///
+    /// A trailing record that does not end with the delimiter counts as a record.
+    pub fn chunks_exact(&self, count: usize, delimiter: char) -> Result<Vec<&[u8]>> {
+        if count == 0 {
+            return Err(ChunkerError::InvalidChunkCount);
+        }
+
+        let data = self.as_bytes();
+        let record_ends = record_ends(data, delimiter as u8);
+        let records = record_ends.len();
+        let count = count.min(records);
+        let mut chunks = Vec::with_capacity(count);
+        let mut start = 0;
+        for i in 1..=count {
+            let end = record_ends[i * records / count - 1];
+            chunks.push(&data[start..end]);
+            start = end;
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide the file into at most `count` delimiter-aligned chunks whose sizes are as even as
+    /// the records allow. Where [`chunks`](Self::chunks) moves each boundary forward to the next
+    /// delimiter, which can leave chunks of quite different sizes, this first finds every
+    /// delimiter and then places each boundary at the record end nearest to its ideal position,
+    /// in either direction. Each chunk is then within about one record of its ideal size.
+    ///
+    /// The extra pass costs a scan of the whole file plus memory for the position of every
+    /// delimiter.
+    pub fn chunks_balanced(&self, count: usize, delimiter: char) -> Result<Vec<&[u8]>> {
+        if count == 0 {
+            return Err(ChunkerError::InvalidChunkCount);
+        }
+
+        let data = self.as_bytes();
+        let record_ends = record_ends(data, delimiter as u8);
+        let mut chunks = Vec::with_capacity(count.min(record_ends.len()));
+        let mut start = 0;
+        for i in 1..count {
+            let ideal = (data.len() as u128 * i as u128 / count as u128) as usize;
+            // The candidates are the record ends on either side of the ideal boundary, not
+            // counting those at or before the previous boundary.
+            let next = record_ends.partition_point(|&end| end < ideal || end <= start);
+            let before = record_ends[..next].last().filter(|&&end| end > start);
+            let after = record_ends.get(next);
+            let end = match (before, after) {
+                (Some(&before), Some(&after)) if ideal - before <= after.abs_diff(ideal) => before,
+                (_, Some(&after)) => after,
+                (Some(&before), None) => before,
+                (None, None) => break,
+            };
+            if end == data.len() {
+                break;
+            }
+            chunks.push(&data[start..end]);
+            start = end;
+        }
+        if start < data.len() {
+            chunks.push(&data[start..]);
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and extend each chunk so that
+    /// it overlaps the start of the next chunk by `overlap` bytes. This is useful for scanning
+    /// for patterns that may straddle a chunk boundary.
+    ///
+    /// If a delimiter is provided, the overlap is extended to end with the next delimiter, so it
+    /// covers whole records. The last chunk has no successor and is never extended, and an
+    /// overlap that reaches the end of the file is truncated there. The total length of the
+    /// chunks exceeds the file size by the sum of the overlaps.
+    pub fn chunks_overlapping(
+        &self,
+        count: usize,
+        overlap: usize,
+        delimiter: Option<char>,
+    ) -> Result<Vec<&[u8]>> {
+        let data = self.as_bytes();
+        Ok(self
+            .boundaries(count, delimiter)?
+            .into_iter()
+            .map(|range| {
+                let mut end = range.end.saturating_add(overlap).min(data.len());
+                if let Some(delimiter) = delimiter {
+                    if end > range.end {
+                        while end < data.len() && data[end - 1] != delimiter as u8 {
+                            end += 1;
+                        }
+                    }
+                }
+                &data[range.start..end]
+            })
+            .collect())
+    }
+
+    /// Divide the file into chunks of approximately equal size, working backward from the end of
+    /// the file, so the newest data in an append-only file comes first. This is useful for
+    /// processing the most recent records of a log before older ones.
+    ///
+    /// Each chunk's start is snapped back to just after the previous delimiter, so chunks still
+    /// end with a delimiter (apart from an unterminated final record). The bytes within each chunk
+    /// are in file order; only the order of the chunks is reversed.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkCount`] if `count` is zero.
+    pub fn chunks_reverse(&self, count: usize, delimiter: Option<char>) -> Result<Vec<&[u8]>> {
+        let chunk_size = self.chunk_size(count)?;
+        let data = self.as_bytes();
+        let mut chunks = Vec::new();
+        let mut end = data.len();
+        while end > 0 {
+            let candidate = end.saturating_sub(chunk_size);
+            let start = match delimiter {
+                Some(delimiter) => {
+                    memchr::memrchr(delimiter as u8, &data[..candidate]).map_or(0, |i| i + 1)
+                }
+                None => candidate,
+            };
+            chunks.push(&data[start..end]);
+            end = start;
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide a file of fixed-size records into at most `count` chunks of approximately equal
+    /// size, where each boundary is rounded down to a multiple of `record_size` so that no record
+    /// is split between chunks. Boundaries that round down to the same record are merged, so a
+    /// file with fewer than `count` records produces fewer chunks.
+    ///
+    /// If the file length is not a multiple of `record_size`, the trailing partial record is
+    /// returned as is, at the end of the last chunk. Use
+    /// [`chunks_aligned_strict`](Self::chunks_aligned_strict) to reject such files instead.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkCount`] if `count` is zero, or
+    /// [`ChunkerError::InvalidChunkSize`] if `record_size` is zero.
+    pub fn chunks_aligned(&self, count: usize, record_size: usize) -> Result<Vec<&[u8]>> {
+        if count == 0 {
+            return Err(ChunkerError::InvalidChunkCount);
+        }
+        if record_size == 0 {
+            return Err(ChunkerError::InvalidChunkSize);
+        }
+
+        let data = self.as_bytes();
+        let mut chunks = Vec::with_capacity(count.min(data.len()));
+        let mut start = 0;
+        for i in 1..count {
+            // Widening keeps `i * len` from overflowing for large files.
+            let ideal = (i as u128 * data.len() as u128 / count as u128) as usize;
+            let end = ideal / record_size * record_size;
+            if end > start {
+                chunks.push(&data[start..end]);
+                start = end;
+            }
+        }
+        if start < data.len() {
+            chunks.push(&data[start..]);
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide a file of fixed-size records into chunks like
+    /// [`chunks_aligned`](Self::chunks_aligned), but first check that the file holds only whole
+    /// records, which catches truncated or mismatched files.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkSize`] if `record_size` is zero, or
+    /// [`ChunkerError::UnalignedLength`] if the file length is not a multiple of `record_size`.
+    pub fn chunks_aligned_strict(&self, count: usize, record_size: usize) -> Result<Vec<&[u8]>> {
+        // `usize::is_multiple_of` would need Rust 1.87.
+        #[allow(clippy::manual_is_multiple_of)]
+        let unaligned = record_size != 0 && self.len % record_size != 0;
+        if unaligned {
+            return Err(ChunkerError::UnalignedLength {
+                len: self.len,
+                record_size,
+            });
+        }
+
+        self.chunks_aligned(count, record_size)
+    }
+
+    /// Returns roughly the last `approx_bytes` bytes of the file, without chunking the rest of
+    /// it. With a delimiter, the start is moved back to just after the preceding delimiter, so
+    /// the slice begins with a full record. This is the first chunk that
+    /// [`chunks_reverse`](Self::chunks_reverse) would produce for the same chunk size.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkSize`] if `approx_bytes` is zero.
+    pub fn tail(&self, approx_bytes: usize, delimiter: Option<char>) -> Result<&[u8]> {
+        if approx_bytes == 0 {
+            return Err(ChunkerError::InvalidChunkSize);
+        }
+
+        let data = self.as_bytes();
+        let candidate = data.len().saturating_sub(approx_bytes);
+        let start = match delimiter {
+            Some(delimiter) => {
+                memchr::memrchr(delimiter as u8, &data[..candidate]).map_or(0, |i| i + 1)
+            }
+            None => candidate,
+        };
+
+        Ok(&data[start..])
+    }
+
+    /// Divide the file into chunks of `records_per_chunk` delimiter-terminated records each, such
+    /// as a fixed number of lines. The last chunk holds the remaining records and may be smaller.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkSize`] if `records_per_chunk` is zero.
+    pub fn chunks_per_records(
+        &self,
+        records_per_chunk: usize,
+        delimiter: char,
+    ) -> Result<Vec<&[u8]>> {
+        if records_per_chunk == 0 {
+            return Err(ChunkerError::InvalidChunkSize);
+        }
+
+        let data = self.as_bytes();
+        let delimiter = delimiter as u8;
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let chunk_ends = memchr::memchr_iter(delimiter, data)
+            .skip(records_per_chunk - 1)
+            .step_by(records_per_chunk);
+        for position in chunk_ends {
+            chunks.push(&data[start..=position]);
+            start = position + 1;
+        }
+        if start < data.len() {
+            chunks.push(&data[start..]);
+        }
+
+        Ok(chunks)
+    }
+
+    /// Lazily divide the file into chunks of approximately equal size. This produces the same
+    /// chunks as [`chunks`](Self::chunks), but computes each boundary only as the iterator is
+    /// advanced.
+    ///
+    /// A `count` of zero is treated as one, producing a single chunk.
+    pub fn chunk_iter(&self, count: usize, delimiter: Option<char>) -> ChunkIter<'_> {
+        let chunk_size = chunk_size(self.len, count).max(1);
+        ChunkIter::new(self.as_bytes(), chunk_size, delimiter.map(|d| d as u8))
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), but return a lazy view that
+    /// supports looking up chunks by index without computing the whole list. See [`Chunks`].
+    ///
+    /// A `count` of zero is treated as one, producing a single chunk.
+    pub fn chunks_ref(&self, count: usize, delimiter: Option<char>) -> Chunks<'_> {
+        Chunks::new(self.as_bytes(), count, delimiter.map(|d| d as u8))
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), checking `cancel` before each
+    /// chunk boundary is searched for. This lets another thread abort a long delimiter search
+    /// over a very large file by setting the flag.
+    ///
+    /// Returns [`ChunkerError::Cancelled`] if `cancel` is set before every chunk is found.
+    pub fn chunks_cancellable(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<&[u8]>> {
+        let chunk_size = self.chunk_size(count)?;
+        let mut chunks = ChunkIter::new(self.as_bytes(), chunk_size, delimiter.map(|d| d as u8));
+        let mut result = Vec::new();
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(ChunkerError::Cancelled);
+            }
+            match chunks.next() {
+                Some(chunk) => result.push(chunk),
+                None => return Ok(result),
+            }
+        }
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and call `progress` with the
+    /// end offset of each chunk as soon as its boundary is found. The last call reports the
+    /// length of the file. This allows showing progress while a slow delimiter search runs over
+    /// a very large file.
+    pub fn chunks_with_progress<F>(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+        mut progress: F,
+    ) -> Result<Vec<&[u8]>>
+    where
+        F: FnMut(usize),
+    {
+        let chunk_size = self.chunk_size(count)?;
+        let data = self.as_bytes();
+        Ok(ChunkIter::new(data, chunk_size, delimiter.map(|d| d as u8))
+            .into_ranges()
+            .map(|range| {
+                progress(range.end);
+                &data[range]
+            })
+            .collect())
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and call `f` with each chunk
+    /// in file order instead of collecting them. No chunk outlives its call, and no `Vec` is
+    /// allocated.
+    pub fn for_each_chunk<F>(&self, count: usize, delimiter: Option<char>, f: F) -> Result<()>
+    where
+        F: FnMut(&[u8]),
+    {
+        let chunk_size = self.chunk_size(count)?;
+        ChunkIter::new(self.as_bytes(), chunk_size, delimiter.map(|d| d as u8)).for_each(f);
+        Ok(())
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and call `f` on each chunk
+    /// using at most `max_in_flight` scoped threads, so no more than `max_in_flight` chunks are
+    /// processed at once. Chunks are handed out lazily as threads become free. Blocks until every
+    /// chunk has been processed.
+    ///
+    /// A `max_in_flight` of zero is treated as one. If `f` panics, the panic is propagated once
+    /// the other threads have finished.
+    pub fn for_each_bounded<F>(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+        max_in_flight: usize,
+        f: F,
+    ) -> Result<()>
+    where
+        F: Fn(&[u8]) + Sync,
+    {
+        let chunk_size = self.chunk_size(count)?;
+        let chunks = Mutex::new(ChunkIter::new(
+            self.as_bytes(),
+            chunk_size,
+            delimiter.map(|d| d as u8),
+        ));
+        let next_chunk = || chunks.lock().unwrap_or_else(|e| e.into_inner()).next();
+        std::thread::scope(|scope| {
+            for _ in 0..max_in_flight.max(1) {
+                scope.spawn(|| {
+                    while let Some(chunk) = next_chunk() {
+                        f(chunk);
+                    }
+                });
+            }
+        });
+        Ok(())
+    }
+
+    /// Count the occurrences of `delimiter` in the whole file, such as the number of lines in a
+    /// newline-delimited file. The scan uses SIMD instructions where available.
+    pub fn count_delimiter(&self, delimiter: u8) -> usize {
+        memchr::memchr_iter(delimiter, self.as_bytes()).count()
+    }
+
+    /// Guess the record delimiter by sampling the first 8 KiB of the file. Returns `\n`, `\0`
+    /// or `\r`, whichever occurs most often in the sample, or `None` if none of them do. Ties
+    /// are resolved in that order, so `\r\n` line endings are detected as `\n`, which is the
+    /// right delimiter for them, and `\r` is only returned for old Mac-style line endings.
+    ///
+    /// This is only a heuristic: a file whose records are longer than the sample, or binary data
+    /// that happens to contain these bytes, may be guessed wrong or not at all.
+    pub fn detect_delimiter(&self) -> Option<u8> {
+        const SAMPLE_LEN: usize = 8 * 1024;
+        const CANDIDATES: [u8; 3] = [b'\n', b'\0', b'\r'];
+
+        let data = self.as_bytes();
+        let sample = &data[..data.len().min(SAMPLE_LEN)];
+        let mut best = None;
+        let mut best_count = 0;
+        for candidate in CANDIDATES {
+            let count = memchr::memchr_iter(candidate, sample).count();
+            if count > best_count {
+                best = Some(candidate);
+                best_count = count;
+            }
+        }
+        best
+    }
+
+    /// Returns the byte offset of the `n`th occurrence of `delimiter`, counting from zero, or
+    /// `None` if the file has `n` or fewer occurrences. For newline-delimited text, the line
+    /// with 0-based index `n + 1` starts just after the returned offset.
+    pub fn nth_delimiter(&self, delimiter: u8, n: usize) -> Option<usize> {
+        memchr::memchr_iter(delimiter, self.as_bytes()).nth(n)
+    }
+
+    /// Merge consecutive groups of `group` chunks into single chunks, such as for a coarser second
+    /// pass over chunks that were already processed. The last merged chunk holds the remaining
+    /// chunks if their number isn't a multiple of `group`. No data is copied: each merged chunk
+    /// is a slice spanning its group.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkCount`] if `group` is zero, or
+    /// [`ChunkerError::NonContiguousChunks`] if the chunks in a group aren't adjacent slices of
+    /// this chunker's data in file order.
+    pub fn coalesce<'a>(&'a self, chunks: &[&'a [u8]], group: usize) -> Result<Vec<&'a [u8]>> {
+        if group == 0 {
+            return Err(ChunkerError::InvalidChunkCount);
+        }
+
+        let data = self.as_bytes();
+        let mut merged = Vec::with_capacity(chunks.len().div_ceil(group));
+        for members in chunks.chunks(group) {
+            let start = offset_in(data, members[0]).ok_or(ChunkerError::NonContiguousChunks)?;
+            let mut end = start;
+            for member in members {
+                if offset_in(data, member) != Some(end) {
+                    return Err(ChunkerError::NonContiguousChunks);
+                }
+                end += member.len();
+            }
+            merged.push(&data[start..end]);
+        }
+
+        Ok(merged)
+    }
+
+    /// Returns whether `chunks` tile the file: they must be slices of this chunker's data, appear
+    /// in file order with no gaps or overlaps, and together cover every byte. Every chunking
+    /// method that covers the whole file should produce chunks that pass this check.
+    pub fn verify_tiling(&self, chunks: &[&[u8]]) -> bool {
+        is_tiling(self.as_bytes(), chunks)
+    }
+
+    /// Returns the chunk at `index` among those that [`chunks`](Self::chunks) would produce for
+    /// the same `count` and `delimiter`, or `None` if there are fewer chunks. This lets each of
+    /// several workers fetch only its own chunk.
+    ///
+    /// Without a delimiter this takes constant time. With a delimiter, every boundary up to
+    /// `index` has to be found first, so the cost grows with `index`, though nothing is
+    /// allocated.
+    pub fn nth_chunk(
+        &self,
+        index: usize,
+        count: usize,
+        delimiter: Option<char>,
+    ) -> Result<Option<&[u8]>> {
+        let chunk_size = self.chunk_size(count)?;
+        let data = self.as_bytes();
+        match delimiter {
+            Some(delimiter) => {
+                Ok(ChunkIter::new(data, chunk_size, Some(delimiter as u8)).nth(index))
+            }
+            None => Ok(index
+                .checked_mul(chunk_size)
+                .filter(|&start| start < data.len())
+                .map(|start| &data[start..start.saturating_add(chunk_size).min(data.len())])),
+        }
+    }
+
+    /// Returns the number of chunks that [`chunks`](Self::chunks) would produce for the same
+    /// arguments, without allocating. Delimiter alignment can make this smaller than `count`.
+    pub fn estimate_chunk_count(&self, count: usize, delimiter: Option<char>) -> Result<usize> {
+        let chunk_size = self.chunk_size(count)?;
+        Ok(ChunkIter::new(self.as_bytes(), chunk_size, delimiter.map(|d| d as u8)).count())
+    }
+
+    /// Divide the file into chunks of approximately equal size, like [`chunks`](Self::chunks),
+    /// with control over which side of the delimiter each boundary falls on.
+    ///
+    /// [`Boundary::TrailingDelimiter`] matches [`chunks`](Self::chunks). With
+    /// [`Boundary::LeadingDelimiter`], each chunk is split just before the next delimiter, so
+    /// every chunk after the first begins with a delimiter. In both cases the chunks tile the
+    /// whole file.
+    pub fn chunks_with_boundary(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+        boundary: Boundary,
+    ) -> Result<Vec<&[u8]>> {
+        let chunk_size = self.chunk_size(count)?;
+        Ok(
+            ChunkIter::new(self.as_bytes(), chunk_size, delimiter.map(|d| d as u8))
+                .with_boundary(boundary)
+                .collect(),
+        )
+    }
+
+    /// Divide the file into chunks of approximately equal size, like [`chunks`](Self::chunks),
+    /// and pair each chunk with its `start..end` byte range in the file. The ranges are
+    /// contiguous and together cover the whole file.
+    pub fn chunks_with_ranges(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+    ) -> Result<Vec<(Range<usize>, &[u8])>> {
+        Ok(self
+            .boundaries(count, delimiter)?
+            .into_iter()
+            .map(|range| (range.clone(), &self.as_bytes()[range]))
+            .collect())
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and pair each chunk with its
+    /// index. Indices start at zero and follow file order, which helps reassemble results that
+    /// are processed out of order, such as in a parallel pipeline.
+    pub fn chunks_enumerated(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+    ) -> Result<Vec<(usize, &[u8])>> {
+        Ok(self
+            .chunks(count, delimiter)?
+            .into_iter()
+            .enumerate()
+            .collect())
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), keeping only the chunks for
+    /// which `pred` returns `true`. Chunk boundaries are computed exactly as for `chunks`, so the
+    /// chunks that are kept don't change when the predicate does.
+    pub fn chunks_matching<F>(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+        pred: F,
+    ) -> Result<Vec<&[u8]>>
+    where
+        F: Fn(&[u8]) -> bool,
+    {
+        let chunk_size = self.chunk_size(count)?;
+        Ok(
+            ChunkIter::new(self.as_bytes(), chunk_size, delimiter.map(|d| d as u8))
+                .filter(|chunk| pred(chunk))
+                .collect(),
+        )
+    }
+
+    /// Compute the `start..end` byte ranges of the chunks that [`chunks`](Self::chunks) would
+    /// produce, without borrowing the file data. The ranges are contiguous and together cover
+    /// the whole file.
+    pub fn boundaries(&self, count: usize, delimiter: Option<char>) -> Result<Vec<Range<usize>>> {
+        self.boundaries_range(0..self.len, count, delimiter)
+    }
+
+    /// Divide only the bytes in `range` into chunks of approximately equal size, like
+    /// [`chunks`](Self::chunks). This is useful for skipping a fixed-size header or trailer.
+    ///
+    /// Returns [`ChunkerError::InvalidRange`] if `range` is inverted or extends past the end of
+    /// the file.
+    pub fn chunks_range(
+        &self,
+        range: Range<usize>,
+        count: usize,
+        delimiter: Option<char>,
+    ) -> Result<Vec<&[u8]>> {
+        let data = self.as_bytes();
+        Ok(self
+            .boundaries_range(range, count, delimiter)?
+            .into_iter()
+            .map(|range| &data[range])
+            .collect())
+    }
+
+    /// Split the file at the given byte offsets, returning the head before the first offset,
+    /// the slices between consecutive offsets, and the tail after the last offset. This
+    /// reproduces a previous chunking exactly, such as when resuming interrupted work from the
+    /// starts of the chunks returned by [`boundaries`](Self::boundaries).
+    ///
+    /// `n` offsets always produce `n + 1` chunks, so an offset of zero, a repeated offset, or an
+    /// offset at the end of the file yields an empty chunk.
+    ///
+    /// Returns [`ChunkerError::InvalidRange`] if the offsets aren't sorted or one extends past the
+    /// end of the file.
+    pub fn chunks_at(&self, offsets: &[usize]) -> Result<Vec<&[u8]>> {
+        let data = self.as_bytes();
+        let mut chunks = Vec::with_capacity(offsets.len() + 1);
+        let mut start = 0;
+        for &end in offsets.iter().chain(std::iter::once(&data.len())) {
+            if end < start || end > data.len() {
+                return Err(ChunkerError::InvalidRange(start..end));
+            }
+            chunks.push(&data[start..end]);
+            start = end;
+        }
+
+        Ok(chunks)
+    }
+
+    /// Compute the byte ranges of the chunks that [`chunks_range`](Self::chunks_range) would
+    /// produce. The returned ranges are absolute offsets into the whole file.
+    pub fn boundaries_range(
+        &self,
+        range: Range<usize>,
+        count: usize,
+        delimiter: Option<char>,
+    ) -> Result<Vec<Range<usize>>> {
+        if range.start > range.end || range.end > self.len {
+            return Err(ChunkerError::InvalidRange(range));
+        }
+        if count == 0 {
+            return Err(ChunkerError::InvalidChunkCount);
+        }
+
+        let window = &self.as_bytes()[range.clone()];
+        let mut boundaries = chunk_boundaries(window, count, delimiter.map(|d| d as u8));
+        for chunk in &mut boundaries {
+            *chunk = (chunk.start + range.start)..(chunk.end + range.start);
+        }
+        Ok(boundaries)
+    }
+
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and write each chunk to its own
+    /// file in `dir`. The files are named `<prefix>_000`, `<prefix>_001`, and so on, and their
+    /// paths are returned in order. Existing files with the same names are overwritten.
+    pub fn split_to_files(
+        &self,
+        count: usize,
+        delimiter: Option<char>,
+        dir: &Path,
+        prefix: &str,
+    ) -> Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        for (i, chunk) in self.chunks(count, delimiter)?.into_iter().enumerate() {
+            let path = dir.join(format!("{}_{:03}", prefix, i));
+            fs::write(&path, chunk)?;
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+
+    /// Divide the file into chunks of at most `max_bytes` bytes. Returns a vector of
+    /// memory-mapped slices that each correspond to a chunk.
+    ///
+    /// If a delimiter is provided, then each chunk is extended to end with the next instance of
+    /// the delimiter, so chunks may be larger than `max_bytes`. A record that is longer than
+    /// `max_bytes` is returned as a single oversized chunk.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkSize`] if `max_bytes` is zero.
+    pub fn chunks_by_size(&self, max_bytes: usize, delimiter: Option<char>) -> Result<Vec<&[u8]>> {
+        if max_bytes == 0 {
+            return Err(ChunkerError::InvalidChunkSize);
+        }
+
+        Ok(ChunkIter::new(self.as_bytes(), max_bytes, delimiter.map(|d| d as u8)).collect())
+    }
+
+    /// Divide the file into chunks of approximately equal size, where each boundary is chosen by
+    /// a predicate. `is_boundary(data, i)` is given the whole file and an index, and returns
+    /// whether a record ends at (and includes) byte `i`. Each chunk ends after the first index at
+    /// or beyond its nominal end for which the predicate returns `true`.
+    ///
+    /// For performance, the predicate is only called while scanning forward from each nominal
+    /// chunk end, not for every byte in the file.
+    pub fn chunks_by<F>(&self, count: usize, is_boundary: F) -> Result<Vec<&[u8]>>
+    where
+        F: Fn(&[u8], usize) -> bool,
+    {
+        let chunk_size = self.chunk_size(count)?;
+        let data = self.as_bytes();
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            let candidate = offset.saturating_add(chunk_size).min(data.len());
+            let chunk_end = (candidate..data.len())
+                .find(|&i| is_boundary(data, i))
+                .map_or(data.len(), |i| i + 1);
+            chunks.push(&data[offset..chunk_end]);
+            offset = chunk_end;
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide the file into chunks of approximately equal size, where each chunk ends with the
+    /// full byte sequence given by `delimiter`. This is useful for multi-byte delimiters such as
+    /// `b"\r\n"` or the UTF-8 encoding of a non-ASCII character.
+    ///
+    /// A delimiter that straddles the nominal chunk boundary is found, and the chunk is split
+    /// after its last byte. If no further delimiter exists, the remainder of the file becomes the
+    /// last chunk.
+    ///
+    /// Returns [`ChunkerError::EmptyDelimiter`] if `delimiter` is empty.
+    pub fn chunks_by_delimiter(&self, count: usize, delimiter: &[u8]) -> Result<Vec<&[u8]>> {
+        if delimiter.is_empty() {
+            return Err(ChunkerError::EmptyDelimiter);
+        }
+
+        let chunk_size = self.chunk_size(count)?;
+        let finder = memchr::memmem::Finder::new(delimiter);
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+        while offset < self.len {
+            let candidate = offset.saturating_add(chunk_size).min(self.len);
+            let search_start = (candidate + 1).saturating_sub(delimiter.len()).max(offset);
+            let chunk_end = match finder.find(&self.as_bytes()[search_start..]) {
+                Some(position) => search_start + position + delimiter.len(),
+                None => self.len,
+            };
+            chunks.push(&self.as_bytes()[offset..chunk_end]);
+            offset = chunk_end;
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide the file into chunks of approximately equal size, where each chunk ends with a
+    /// complete framed record, such as `<...>`. Frames may be nested: a boundary is only placed
+    /// after a `close` byte that returns the nesting depth to zero.
+    ///
+    /// A `close` byte outside any frame is ignored. If `open` and `close` are the same byte,
+    /// frames can't nest, and occurrences alternate between opening and closing a frame, as with
+    /// quotes.
+    pub fn chunks_framed(&self, count: usize, open: u8, close: u8) -> Result<Vec<&[u8]>> {
+        let chunk_size = self.chunk_size(count)?;
+        let data = self.as_bytes();
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+        let mut depth = 0usize;
+        let mut i = 0;
+        while offset < data.len() {
+            let candidate = offset.saturating_add(chunk_size).min(data.len());
+            let mut chunk_end = data.len();
+            while i < data.len() {
+                let b = data[i];
+                i += 1;
+                if b == close && depth > 0 {
+                    depth -= 1;
+                    if depth == 0 && i > candidate {
+                        chunk_end = i;
+                        break;
+                    }
+                } else if b == open {
+                    depth += 1;
+                }
+            }
+            chunks.push(&data[offset..chunk_end]);
+            offset = chunk_end;
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide the file into chunks of approximately equal size, where each chunk ends with
+    /// `delimiter`, ignoring delimiters between a pair of `quote` bytes. This keeps quoted CSV
+    /// fields that contain newlines intact.
+    ///
+    /// Each `quote` byte toggles whether a region is quoted, so quotes escaped by doubling them,
+    /// as in `"say ""hi"""`, work as expected. Quotes escaped any other way, such as `\"`, are
+    /// treated as ordinary quotes and will desynchronize the tracking for the rest of the file.
+    pub fn chunks_escaped(&self, count: usize, delimiter: u8, quote: u8) -> Result<Vec<&[u8]>> {
+        let chunk_size = self.chunk_size(count)?;
+        let data = self.as_bytes();
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+        let mut quoted = false;
+        let mut i = 0;
+        while offset < data.len() {
+            let candidate = offset.saturating_add(chunk_size).min(data.len());
+            let mut chunk_end = data.len();
+            while i < data.len() {
+                let b = data[i];
+                i += 1;
+                if b == quote {
+                    quoted = !quoted;
+                } else if b == delimiter && !quoted && i > candidate {
+                    chunk_end = i;
+                    break;
+                }
+            }
+            chunks.push(&data[offset..chunk_end]);
+            offset = chunk_end;
+        }
+
+        Ok(chunks)
+    }
+
+    /// Divide the file into chunks of approximately equal size, where each chunk ends with any
+    /// one of the bytes in `delimiters`. This is useful for input that mixes record separators,
+    /// such as `\n` and `\0`.
+    ///
+    /// Returns [`ChunkerError::EmptyDelimiter`] if `delimiters` is empty.
+    pub fn chunks_any_delimiter(&self, count: usize, delimiters: &[u8]) -> Result<Vec<&[u8]>> {
+        if delimiters.is_empty() {
+            return Err(ChunkerError::EmptyDelimiter);
+        }
+
+        // A lookup table makes membership a single index per byte, however many delimiters
+        // there are.
+        let mut is_delimiter = [false; 256];
+        for &d in delimiters {
+            is_delimiter[d as usize] = true;
+        }
+        self.chunks_by(count, |data, i| is_delimiter[data[i] as usize])
+    }
+
+    fn chunk_size(&self, count: usize) -> Result<usize> {
+        if count == 0 {
+            return Err(ChunkerError::InvalidChunkCount);
+        }
+        Ok(chunk_size(self.len, count).max(1))
+    }
+}
+
+/// Divide `data` into `count` chunks of approximately equal size. This is the algorithm behind
+/// [`FileChunker::chunks`] and [`SliceChunker::chunks`].
+fn split(data: &[u8], count: usize, delimiter: Option<u8>) -> Result<Vec<&[u8]>> {
+    // Every chunk but the last is at least `chunk_size` long, so there are at most `count`.
+    split_with_capacity(data, count, delimiter, count.min(data.len()))
+}
+
+fn split_with_capacity(
+    data: &[u8],
+    count: usize,
+    delimiter: Option<u8>,
+    capacity: usize,
+) -> Result<Vec<&[u8]>> {
+    if count == 0 {
+        return Err(ChunkerError::InvalidChunkCount);
+    }
+    let chunk_size = chunk_size(data.len(), count).max(1);
+    let mut chunks = Vec::with_capacity(capacity);
+    chunks.extend(ChunkIter::new(data, chunk_size, delimiter));
+    debug_assert!(is_tiling(data, &chunks));
+    Ok(chunks)
+}
+
+// Like `split`, but for UTF-16 text: chunk sizes are whole code units, and the delimiter is only
+// matched at code unit boundaries.
+fn split_utf16(data: &[u8], count: usize, delimiter: Option<[u8; 2]>) -> Result<Vec<&[u8]>> {
+    if count == 0 {
+        return Err(ChunkerError::InvalidChunkCount);
+    }
+    let chunk_size = chunk_size(data.len() / 2, count).max(1) * 2;
+    let mut chunks = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let candidate = offset.saturating_add(chunk_size).min(data.len());
+        let chunk_end = match delimiter {
+            Some(delimiter) => data[candidate..]
+                .chunks_exact(2)
+                .position(|unit| unit == delimiter)
+                .map_or(data.len(), |i| candidate + 2 * i + 2),
+            None => candidate,
+        };
+        chunks.push(&data[offset..chunk_end]);
+        offset = chunk_end;
+    }
+
+    Ok(chunks)
+}
+
+// Returns the offset of `chunk` within `data`, or `None` if it isn't a subslice of `data`.
+fn offset_in(data: &[u8], chunk: &[u8]) -> Option<usize> {
+    let offset = (chunk.as_ptr() as usize).checked_sub(data.as_ptr() as usize)?;
+    (offset.checked_add(chunk.len())? <= data.len()).then_some(offset)
+}
+
+// Returns the offset just past each record, counting a trailing record without a delimiter.
+fn record_ends(data: &[u8], delimiter: u8) -> Vec<usize> {
+    let mut ends: Vec<usize> = memchr::memchr_iter(delimiter, data)
+        .map(|i| i + 1)
+        .collect();
+    if !data.is_empty() && ends.last() != Some(&data.len()) {
+        ends.push(data.len());
+    }
+    ends
+}
+
+fn is_tiling(data: &[u8], chunks: &[&[u8]]) -> bool {
+    let mut next = data.as_ptr() as usize;
+    for chunk in chunks {
+        if chunk.as_ptr() as usize != next {
+            return false;
+        }
+        next += chunk.len();
+    }
+    next == data.as_ptr() as usize + data.len()
+}
+
+fn chunk_size(file_size: usize, count: usize) -> usize {
+    // Integer ceiling division: unlike `(file_size + count - 1) / count` this cannot overflow, and
+    // unlike going through `f64` it stays exact for sizes above 2^53.
+    file_size.div_ceil(count.max(1))
+}
+
+fn cpu_count() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+fn with_retries<T>(retries: usize, mut f: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    let mut delay = Duration::from_millis(1);
+    let mut attempt = 0;
+    loop {
+        match f() {
+            Err(err) if attempt < retries && is_transient(&err) => {
+                std::thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_transient(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+fn check_addressable(file: &File) -> Result<()> {
+    addressable_len(file.metadata()?.len()).map(|_| ())
+}
+
+fn addressable_len(size: u64) -> Result<usize> {
+    usize::try_from(size).map_err(|_| ChunkerError::FileTooLarge { size })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use std::io::{Seek, Write};
+
+    const HAPROXY_LOG: &str = "Nov 23 06:26:40 ip-10-1-1-1 haproxy[20128]: 10.1.1.10:57305 [23/Nov/2019:06:26:40.781] public myapp/i-05fa49c0e7db8c328 0/0/0/78/78 206 913/458 - - ---- 9/9/6/0/0 0/0 {bytes=0-0} {||1|bytes 0-0/499704} \"GET /2518cb13a48bdf53b2f936f44e7042a3cc7baa06 HTTP/1.1\"
+Nov 23 06:26:41 ip-10-1-1-1 haproxy[20128]: 10.1.1.11:51819 [23/Nov/2019:06:27:41.780] public myapp/i-059c225b48702964a 0/0/0/80/80 200 802/142190 - - ---- 8/8/5/0/0 0/0 {} {||141752|} \"GET /2043f2eb9e2691edcc0c8084d1ffce8bd70bc6e7 HTTP/1.1\"
+Nov 23 06:26:42 ip-10-1-1-1 haproxy[20128]: 10.1.1.12:38870 [23/Nov/2019:06:28:42.773] public myapp/i-048088fd46abe7ed0 0/0/0/77/100 200 823/512174 - - ---- 8/8/5/0/0 0/0 {} {||511736|} \"GET /eb59c0b5dad36f080f3d261c6257ce0e21ef1a01 HTTP/1.1\"
+Nov 23 06:26:43 ip-10-1-1-1 haproxy[20128]: 10.1.1.13:35528 [23/Nov/2019:06:29:43.775] public myapp/i-05e9315b035d50f62 0/0/0/103/105 200 869/431481 - - ---- 8/8/1/0/0 0/0 {} {|||} \"GET /164672c9d75c76a8fa237c24f9cbfd2222554f6d HTTP/1.1\"
+Nov 23 06:26:44 ip-10-1-1-1 haproxy[20128]: 10.1.1.14:48553 [23/Nov/2019:06:30:44.808] public myapp/i-0008bfe6b1c98e964 0/0/0/72/73 200 840/265518 - - ---- 7/7/5/0/0 0/0 {} {||265080|} \"GET /e3b526928196d19ab3419d433f3de0ceb71e62b5 HTTP/1.1\"
+Nov 23 06:26:45 ip-10-1-1-1 haproxy[20128]: 10.1.1.15:60969 [23/Nov/2019:06:31:45.727] public myapp/i-005a2bfdba4c405a8 0/0/0/146/167 200 852/304622 - - ---- 7/7/5/0/0 0/0 {} {||304184|} \"GET /52f5edb4a46276defe54ead2fae3a19fb8cafdb6 HTTP/1.1\"
+Nov 23 06:26:46 ip-10-1-1-1 haproxy[20128]: 10.1.1.14:48539 [23/Nov/2019:06:32:46.730] public myapp/i-03b180605be4fa176 0/0/0/171/171 200 889/124142 - - ---- 6/6/4/0/0 0/0 {} {||123704|} \"GET /ef9e0c85cc1c76d7dc777f5b19d7cb85478496e4 HTTP/1.1\"
+Nov 23 06:26:47 ip-10-1-1-1 haproxy[20128]: 10.1.1.11:51847 [23/Nov/2019:06:33:47.886] public myapp/i-0aa566420409956d6 0/0/0/28/28 206 867/458 - - ---- 6/6/4/0/0 0/0 {bytes=0-0} {} \"GET /3c7ace8c683adcad375a4d14995734ac0db08bb3 HTTP/1.1\"
+Nov 23 06:26:48 ip-10-1-1-1 haproxy[20128]: 10.1.1.13:35554 [23/Nov/2019:06:34:48.866] public myapp/i-07f4205f35b4774b6 0/0/0/23/49 200 816/319662 - - ---- 5/5/3/0/0 0/0 {} {||319224|} \"GET /b95db0578977cd32658fa28b386c0db67ab23ee7 HTTP/1.1\"
+Nov 23 06:26:49 ip-10-1-1-1 haproxy[20128]: 10.1.1.12:38899 [23/Nov/2019:06:35:49.879] public myapp/i-08cb5309afd22e8c0 0/0/0/59/59 200 1000/112110 - - ---- 5/5/3/0/0 0/0 {} {||111672|} \"GET /5314ca870ed0f5e48a71adca185e4ff7f1d9d80f HTTP/1.1\"
+";
+
+    #[test]
+    fn chunks_with_delimiter() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n45\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89");
+    }
+
+    #[test]
+    fn chunks_without_delimiter() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(10, None).unwrap();
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(chunks.len(), 10);
+        (0..9).for_each(|i| {
+            assert_eq!(String::from_utf8_lossy(chunks[i]), format!("{}", i));
+        });
+    }
+
+    #[test]
+    fn chunks_with_delimiter_empty() {
+        let log = "";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 0);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+    }
+
+    #[test]
+    fn chunks_without_delimiter_empty() {
+        let log = "";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(2, None).unwrap();
+        assert_eq!(chunks.len(), 0);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+    }
+
+    #[test]
+    fn chunks_with_delimiter_start_and_end() {
+        let log = "\n01\n23\n45\n67\n89\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "\n01\n23\n45\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89\n");
+    }
+
+    #[test]
+    fn chunks_with_delimiter_only() {
+        let log = "\n\n\n\n\n\n\n\n\n\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "\n\n\n\n\n\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "\n\n\n\n");
+    }
+
+    #[test]
+    fn chunks_with_nonexistent_delimiter() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(10, Some('\n')).unwrap();
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0], b"0123456789");
+    }
+
+    #[test]
+    fn chunks_with_delimiter_long_text() {
+        let log = "Nov 23 06:26:40 ip-10-1-1-1 haproxy[20128]: 10.1.1.10:57305 [23/Nov/2019:06:26:40.781] public myapp/i-05fa49c0e7db8c328 0/0/0/78/78 206 913/458 - - ---- 9/9/6/0/0 0/0 {bytes=0-0} {||1|bytes 0-0/499704} \"GET /2518cb13a48bdf53b2f936f44e7042a3cc7baa06 HTTP/1.1\"
+Nov 23 06:26:41 ip-10-1-1-1 haproxy[20128]: 10.1.1.11:51819 [23/Nov/2019:06:27:41.780] public myapp/i-059c225b48702964a 0/0/0/80/80 200 802/142190 - - ---- 8/8/5/0/0 0/0 {} {||141752|} \"GET /2043f2eb9e2691edcc0c8084d1ffce8bd70bc6e7 HTTP/1.1\"
+Nov 23 06:26:42 ip-10-1-1-1 haproxy[20128]: 10.1.1.12:38870 [23/Nov/2019:06:28:42.773] public myapp/i-048088fd46abe7ed0 0/0/0/77/100 200 823/512174 - - ---- 8/8/5/0/0 0/0 {} {||511736|} \"GET /eb59c0b5dad36f080f3d261c6257ce0e21ef1a01 HTTP/1.1\"
+Nov 23 06:26:43 ip-10-1-1-1 haproxy[20128]: 10.1.1.13:35528 [23/Nov/2019:06:29:43.775] public myapp/i-05e9315b035d50f62 0/0/0/103/105 200 869/431481 - - ---- 8/8/1/0/0 0/0 {} {|||} \"GET /164672c9d75c76a8fa237c24f9cbfd2222554f6d HTTP/1.1\"
+Nov 23 06:26:44 ip-10-1-1-1 haproxy[20128]: 10.1.1.14:48553 [23/Nov/2019:06:30:44.808] public myapp/i-0008bfe6b1c98e964 0/0/0/72/73 200 840/265518 - - ---- 7/7/5/0/0 0/0 {} {||265080|} \"GET /e3b526928196d19ab3419d433f3de0ceb71e62b5 HTTP/1.1\"
+Nov 23 06:26:45 ip-10-1-1-1 haproxy[20128]: 10.1.1.15:60969 [23/Nov/2019:06:31:45.727] public myapp/i-005a2bfdba4c405a8 0/0/0/146/167 200 852/304622 - - ---- 7/7/5/0/0 0/0 {} {||304184|} \"GET /52f5edb4a46276defe54ead2fae3a19fb8cafdb6 HTTP/1.1\"
+Nov 23 06:26:46 ip-10-1-1-1 haproxy[20128]: 10.1.1.14:48539 [23/Nov/2019:06:32:46.730] public myapp/i-03b180605be4fa176 0/0/0/171/171 200 889/124142 - - ---- 6/6/4/0/0 0/0 {} {||123704|} \"GET /ef9e0c85cc1c76d7dc777f5b19d7cb85478496e4 HTTP/1.1\"
+Nov 23 06:26:47 ip-10-1-1-1 haproxy[20128]: 10.1.1.11:51847 [23/Nov/2019:06:33:47.886] public myapp/i-0aa566420409956d6 0/0/0/28/28 206 867/458 - - ---- 6/6/4/0/0 0/0 {bytes=0-0} {} \"GET /3c7ace8c683adcad375a4d14995734ac0db08bb3 HTTP/1.1\"
+Nov 23 06:26:48 ip-10-1-1-1 haproxy[20128]: 10.1.1.13:35554 [23/Nov/2019:06:34:48.866] public myapp/i-07f4205f35b4774b6 0/0/0/23/49 200 816/319662 - - ---- 5/5/3/0/0 0/0 {} {||319224|} \"GET /b95db0578977cd32658fa28b386c0db67ab23ee7 HTTP/1.1\"
+Nov 23 06:26:49 ip-10-1-1-1 haproxy[20128]: 10.1.1.12:38899 [23/Nov/2019:06:35:49.879] public myapp/i-08cb5309afd22e8c0 0/0/0/59/59 200 1000/112110 - - ---- 5/5/3/0/0 0/0 {} {||111672|} \"GET /5314ca870ed0f5e48a71adca185e4ff7f1d9d80f HTTP/1.1\"
+";
+        let log_lines: Vec<_> = log.lines().collect();
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(5, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 4); // N.B.: This is smaller than requested because we're chunking based on the delimeter
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(
+            String::from_utf8_lossy(chunks[0]),
+            format!("{}\n{}\n", log_lines[0], &log_lines[1])
+        );
+        assert_eq!(
+            String::from_utf8_lossy(chunks[1]),
+            format!("{}\n{}\n{}\n", log_lines[2], &log_lines[3], &log_lines[4])
+        );
+        assert_eq!(
+            String::from_utf8_lossy(chunks[2]),
+            format!("{}\n{}\n{}\n", log_lines[5], &log_lines[6], &log_lines[7])
+        );
+        assert_eq!(
+            String::from_utf8_lossy(chunks[3]),
+            format!("{}\n{}\n", log_lines[8], &log_lines[9])
+        );
+    }
+
+    #[test]
+    #[allow(clippy::erasing_op, clippy::identity_op)]
+    fn chunks_without_delimiter_long_text() {
+        let log = "Nov 23 06:26:40 ip-10-1-1-1 haproxy[20128]: 10.1.1.10:57305 [23/Nov/2019:06:26:40.781] public myapp/i-05fa49c0e7db8c328 0/0/0/78/78 206 913/458 - - ---- 9/9/6/0/0 0/0 {bytes=0-0} {||1|bytes 0-0/499704} \"GET /2518cb13a48bdf53b2f936f44e7042a3cc7baa06 HTTP/1.1\"
+Nov 23 06:26:41 ip-10-1-1-1 haproxy[20128]: 10.1.1.11:51819 [23/Nov/2019:06:27:41.780] public myapp/i-059c225b48702964a 0/0/0/80/80 200 802/142190 - - ---- 8/8/5/0/0 0/0 {} {||141752|} \"GET /2043f2eb9e2691edcc0c8084d1ffce8bd70bc6e7 HTTP/1.1\"
+Nov 23 06:26:42 ip-10-1-1-1 haproxy[20128]: 10.1.1.12:38870 [23/Nov/2019:06:28:42.773] public myapp/i-048088fd46abe7ed0 0/0/0/77/100 200 823/512174 - - ---- 8/8/5/0/0 0/0 {} {||511736|} \"GET /eb59c0b5dad36f080f3d261c6257ce0e21ef1a01 HTTP/1.1\"
+Nov 23 06:26:43 ip-10-1-1-1 haproxy[20128]: 10.1.1.13:35528 [23/Nov/2019:06:29:43.775] public myapp/i-05e9315b035d50f62 0/0/0/103/105 200 869/431481 - - ---- 8/8/1/0/0 0/0 {} {|||} \"GET /164672c9d75c76a8fa237c24f9cbfd2222554f6d HTTP/1.1\"
+Nov 23 06:26:44 ip-10-1-1-1 haproxy[20128]: 10.1.1.14:48553 [23/Nov/2019:06:30:44.808] public myapp/i-0008bfe6b1c98e964 0/0/0/72/73 200 840/265518 - - ---- 7/7/5/0/0 0/0 {} {||265080|} \"GET /e3b526928196d19ab3419d433f3de0ceb71e62b5 HTTP/1.1\"
+Nov 23 06:26:45 ip-10-1-1-1 haproxy[20128]: 10.1.1.15:60969 [23/Nov/2019:06:31:45.727] public myapp/i-005a2bfdba4c405a8 0/0/0/146/167 200 852/304622 - - ---- 7/7/5/0/0 0/0 {} {||304184|} \"GET /52f5edb4a46276defe54ead2fae3a19fb8cafdb6 HTTP/1.1\"
+Nov 23 06:26:46 ip-10-1-1-1 haproxy[20128]: 10.1.1.14:48539 [23/Nov/2019:06:32:46.730] public myapp/i-03b180605be4fa176 0/0/0/171/171 200 889/124142 - - ---- 6/6/4/0/0 0/0 {} {||123704|} \"GET /ef9e0c85cc1c76d7dc777f5b19d7cb85478496e4 HTTP/1.1\"
+Nov 23 06:26:47 ip-10-1-1-1 haproxy[20128]: 10.1.1.11:51847 [23/Nov/2019:06:33:47.886] public myapp/i-0aa566420409956d6 0/0/0/28/28 206 867/458 - - ---- 6/6/4/0/0 0/0 {bytes=0-0} {} \"GET /3c7ace8c683adcad375a4d14995734ac0db08bb3 HTTP/1.1\"
+Nov 23 06:26:48 ip-10-1-1-1 haproxy[20128]: 10.1.1.13:35554 [23/Nov/2019:06:34:48.866] public myapp/i-07f4205f35b4774b6 0/0/0/23/49 200 816/319662 - - ---- 5/5/3/0/0 0/0 {} {||319224|} \"GET /b95db0578977cd32658fa28b386c0db67ab23ee7 HTTP/1.1\"
+Nov 23 06:26:49 ip-10-1-1-1 haproxy[20128]: 10.1.1.12:38899 [23/Nov/2019:06:35:49.879] public myapp/i-08cb5309afd22e8c0 0/0/0/59/59 200 1000/112110 - - ---- 5/5/3/0/0 0/0 {} {||111672|} \"GET /5314ca870ed0f5e48a71adca185e4ff7f1d9d80f HTTP/1.1\"
+";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunks = 5;
+        let chunk_size = log.len() / chunks;
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(chunks, None).unwrap();
+
+        assert_eq!(chunks.len(), 5);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(
+            String::from_utf8_lossy(chunks[0]),
+            log[(0 * chunk_size)..=(1 * chunk_size)].to_string()
+        );
+        assert_eq!(
+            String::from_utf8_lossy(chunks[1]),
+            log[(1 * (chunk_size + 1))..=(2 * chunk_size + 1)].to_string()
+        );
+        assert_eq!(
+            String::from_utf8_lossy(chunks[2]),
+            log[(2 * (chunk_size + 1))..=(3 * chunk_size + 2)].to_string()
+        );
+        assert_eq!(
+            String::from_utf8_lossy(chunks[3]),
+            log[(3 * (chunk_size + 1))..=(4 * chunk_size + 3)].to_string()
+        );
+        assert_eq!(
+            String::from_utf8_lossy(chunks[4]),
+            log[(4 * (chunk_size + 1))..].to_string()
+        );
+    }
+
+    #[test]
+    fn chunks_with_delimiter_single_chunk() {
+        let log = "Why, sometimes I've believed as many as six impossible things before breakfast.";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunk_count = 1;
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(chunk_count, Some('\n')).unwrap();
+
+        assert_eq!(chunks[0].len(), log.len());
+        assert_eq!(chunks.len(), 1);
+    }
+
+    #[test]
+    fn chunks_by_size_with_delimiter() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_by_size(4, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "45\n67\n");
+        assert_eq!(String::from_utf8_lossy(chunks[2]), "89");
+    }
+
+    #[test]
+    fn chunks_by_size_without_delimiter() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_by_size(4, None).unwrap();
+        assert_eq!(chunks, vec![&b"0123"[..], b"4567", b"89"]);
+    }
+
+    #[test]
+    fn chunks_by_size_oversized_record() {
+        let log = "0123456789\n01\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_by_size(2, Some('\n')).unwrap();
+        assert_eq!(chunks, vec![&b"0123456789\n"[..], b"01\n"]);
+
+        let chunks = chunker.chunks_by_size(usize::MAX, Some('\n')).unwrap();
+        assert_eq!(chunks, vec![log.as_bytes()]);
+    }
+
+    #[test]
+    fn chunks_by_size_zero() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert!(matches!(
+            chunker.chunks_by_size(0, None),
+            Err(ChunkerError::InvalidChunkSize)
+        ));
+    }
+
+    #[test]
+    fn chunks_by_delimiter_crlf() {
+        let log = "01\r\n23\r\n45\r\n67\r\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_by_delimiter(2, b"\r\n").unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\r\n23\r\n45\r\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\r\n89");
+    }
+
+    #[test]
+    fn chunks_by_delimiter_straddling_boundary() {
+        let log = "0123\r\n45678\r\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        // The nominal boundary falls between the '\r' and the '\n'.
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_by_delimiter(3, b"\r\n").unwrap();
+        assert_eq!(chunks, vec![&b"0123\r\n"[..], b"45678\r\n"]);
+    }
+
+    #[test]
+    fn chunks_by_delimiter_multibyte_char() {
+        let log = "01€23€45€67€89€";
+        let mut delimiter = [0; 4];
+        let delimiter = '€'.encode_utf8(&mut delimiter).as_bytes();
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_by_delimiter(2, delimiter).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(std::str::from_utf8(chunks[0]).unwrap(), "01€23€45€");
+        assert_eq!(std::str::from_utf8(chunks[1]).unwrap(), "67€89€");
+    }
+
+    #[test]
+    fn chunks_by_delimiter_partial_match_at_end() {
+        let log = "0123456789\r";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_by_delimiter(4, b"\r\n").unwrap();
+        assert_eq!(chunks, vec![log.as_bytes()]);
+        assert!(matches!(
+            chunker.chunks_by_delimiter(4, b""),
+            Err(ChunkerError::EmptyDelimiter)
+        ));
+    }
+
+    #[test]
+    fn open_path() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::open(file.path()).unwrap();
+        let chunks = chunker.chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n45\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89");
+    }
+
+    #[test]
+    fn open_missing_path() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            FileChunker::open(dir.path().join("missing")),
+            Err(ChunkerError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn chunks_with_ranges_tile_file() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_with_ranges(2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].0, 0..9);
+        assert_eq!(chunks[1].0, 9..log.len());
+        for (range, chunk) in chunks {
+            assert_eq!(&log.as_bytes()[range], chunk);
+        }
+    }
+
+    #[test]
+    fn chunks_zero_count() {
+        let log = "";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert!(matches!(
+            chunker.chunks(0, None),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+
+    #[test]
+    fn chunks_zero_count_with_delimiter() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert!(matches!(
+            chunker.chunks(0, Some('\n')),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+        assert!(matches!(
+            chunker.chunks(0, None),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+        assert!(matches!(
+            chunker.chunks_with_ranges(0, Some('\n')),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+        assert!(matches!(
+            chunker.chunks_by_delimiter(0, b"\n"),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+        assert_eq!(
+            chunker.chunk_iter(0, Some('\n')).collect::<Vec<_>>(),
+            vec![log.as_bytes()]
+        );
+    }
+
+    #[test]
+    fn chunks_with_leading_delimiter() {
+        let log = "\x1e01\x1e23\x1e45\x1e67\x1e89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .chunks_with_boundary(2, Some('\x1e'), Boundary::LeadingDelimiter)
+            .unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "\x1e01\x1e23\x1e45");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "\x1e67\x1e89");
+
+        let trailing = chunker
+            .chunks_with_boundary(2, Some('\x1e'), Boundary::TrailingDelimiter)
+            .unwrap();
+        assert_eq!(trailing, chunker.chunks(2, Some('\x1e')).unwrap());
+    }
+
+    #[test]
+    fn chunks_with_len() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap().with_len(8);
+        let chunks = chunker.chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), 8);
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "45");
+
+        let chunker = chunker.with_len(usize::MAX);
+        let chunks = chunker.chunks(1, None).unwrap();
+        assert_eq!(chunks, vec![log.as_bytes()]);
+    }
+
+    #[test]
+    fn chunks_ignore_appended_data() {
+        let log = "01\n23\n45\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        file.write_all(b"67\n89\n").unwrap();
+        file.flush().unwrap();
+
+        let chunks = chunker.chunks(1, Some('\n')).unwrap();
+        assert_eq!(chunks, vec![log.as_bytes()]);
+    }
+
+    #[test]
+    fn chunks_trim_delimiter() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_trim_delimiter(2, '\n').unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len() - 1);
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n45");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89");
+    }
+
+    #[test]
+    fn chunks_trim_delimiter_at_end_of_file() {
+        let log = "01\n23\n45\n67\n89\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_trim_delimiter(2, '\n').unwrap();
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len() - 2);
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n45");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89");
+    }
+
+    #[test]
+    fn boundaries_match_chunks() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let boundaries = chunker.boundaries(2, Some('\n')).unwrap();
+        assert_eq!(boundaries, vec![0..9, 9..log.len()]);
+
+        let chunks = chunker.chunks(3, None).unwrap();
+        let boundaries = chunker.boundaries(3, None).unwrap();
+        assert_eq!(boundaries.len(), chunks.len());
+        for (range, chunk) in boundaries.into_iter().zip(chunks) {
+            assert_eq!(&log.as_bytes()[range], chunk);
+        }
+    }
+
+    #[test]
+    fn chunks_utf8_on_char_boundaries() {
+        let log = "aé€😀aé€😀aé€😀";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for count in 1..=log.len() {
+            let chunks = chunker.chunks_utf8(count).unwrap();
+            assert!(chunks.len() <= count);
+            assert_eq!(chunks.concat(), log);
+        }
+        assert_eq!(
+            chunker.chunks_utf8(3).unwrap(),
+            vec!["aé€😀", "aé€😀", "aé€😀"]
+        );
+    }
+
+    #[test]
+    fn chunks_utf8_invalid() {
+        let log = b"01\n\xff\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert!(matches!(chunker.chunks_utf8(2), Err(ChunkerError::Utf8(_))));
+    }
+
+    #[test]
+    fn new_buffered() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+        file.rewind().unwrap();
+
+        let chunker = FileChunker::new_buffered(&file).unwrap();
+        let chunks = chunker.chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n45\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89");
+    }
+
+    #[test]
+    fn chunker_trait_is_backing_agnostic() {
+        fn chunk_count<C: Chunker>(chunker: &C) -> usize {
+            chunker.chunks(2, Some('\n')).unwrap().len()
+        }
+
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+        file.rewind().unwrap();
+
+        let mapped = FileChunker::new(&file).unwrap();
+        let buffered = FileChunker::new_buffered(&file).unwrap();
+        assert_eq!(chunk_count(&mapped), 2);
+        assert_eq!(chunk_count(&buffered), 2);
+    }
+
+    #[test]
+    fn advise() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+        file.rewind().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        chunker.advise_sequential().unwrap();
+        chunker.advise_willneed().unwrap();
+        assert_eq!(chunker.chunks(2, Some('\n')).unwrap().len(), 2);
+
+        let chunker = FileChunker::new_buffered(&file).unwrap();
+        chunker.advise_sequential().unwrap();
+        chunker.advise_willneed().unwrap();
+
+        let empty: File = tempfile::tempfile().unwrap();
+        let chunker = FileChunker::new(&empty).unwrap();
+        chunker.advise_sequential().unwrap();
+    }
+
+    #[test]
+    fn chunks_with_counts() {
+        let log = HAPROXY_LOG;
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_with_counts(5, '\n').unwrap();
+        assert_eq!(
+            chunks.iter().map(|(_, count)| *count).collect::<Vec<_>>(),
+            vec![2, 3, 3, 2]
+        );
+        assert_eq!(
+            chunks.iter().map(|(_, count)| count).sum::<usize>(),
+            log.lines().count()
+        );
+    }
+
+    #[test]
+    fn chunks_exact() {
+        let log = HAPROXY_LOG;
+        let log_lines: Vec<_> = log.lines().collect();
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_exact(5, '\n').unwrap();
+        assert_eq!(chunks.len(), 5);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert_eq!(
+                String::from_utf8_lossy(chunk),
+                format!("{}\n{}\n", log_lines[2 * i], log_lines[2 * i + 1])
+            );
+        }
+    }
+
+    #[test]
+    fn chunks_exact_fewer_records_than_count() {
+        let log = "01\n23\n45";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_exact(2, '\n').unwrap();
+        assert_eq!(chunks, vec![&b"01\n"[..], b"23\n45"]);
+
+        let chunks = chunker.chunks_exact(10, '\n').unwrap();
+        assert_eq!(chunks, vec![&b"01\n"[..], b"23\n", b"45"]);
+    }
+
+    #[test]
+    fn chunks_overlapping_without_delimiter() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_overlapping(3, 2, None).unwrap();
+        assert_eq!(chunks, vec![&b"012345"[..], b"456789", b"89"]);
+        assert_eq!(
+            chunker.chunks_overlapping(3, 0, None).unwrap(),
+            chunker.chunks(3, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn chunks_overlapping_with_delimiter() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_overlapping(2, 1, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n45\n67\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89");
+    }
+
+    #[test]
+    fn chunks_range() {
+        let log = "HEAD01\n23\n45\n67\n89TAIL";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let range = 4..(log.len() - 4);
+        let chunks = chunker.chunks_range(range.clone(), 2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n45\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89");
+
+        let boundaries = chunker.boundaries_range(range, 2, Some('\n')).unwrap();
+        assert_eq!(boundaries, vec![4..13, 13..(log.len() - 4)]);
+    }
+
+    #[test]
+    fn chunks_range_invalid() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert!(matches!(
+            chunker.chunks_range(5..11, 2, None),
+            Err(ChunkerError::InvalidRange(_))
+        ));
+        #[allow(clippy::reversed_empty_ranges)]
+        let inverted = 5..4;
+        assert!(matches!(
+            chunker.chunks_range(inverted, 2, None),
+            Err(ChunkerError::InvalidRange(_))
+        ));
+        assert!(chunker.chunks_range(5..5, 2, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn len_and_is_empty() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(chunker.len(), log.len());
+        assert!(!chunker.is_empty());
+
+        let chunker = chunker.with_len(0);
+        assert_eq!(chunker.len(), 0);
+        assert!(chunker.is_empty());
+
+        let empty: File = tempfile::tempfile().unwrap();
+        let chunker = FileChunker::new(&empty).unwrap();
+        assert!(chunker.is_empty());
+    }
+
+    #[test]
+    fn as_bytes() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(chunker.as_bytes(), log.as_bytes());
+        assert_eq!(
+            chunker.chunks(2, Some('\n')).unwrap().concat(),
+            chunker.as_bytes()
+        );
+        assert_eq!(chunker.with_len(5).as_bytes(), b"01\n23");
+    }
+
+    #[test]
+    fn chunks_into_reuses_buffer() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let mut chunks = Vec::with_capacity(16);
+        chunker.chunks_into(2, Some('\n'), &mut chunks).unwrap();
+        assert_eq!(chunks, chunker.chunks(2, Some('\n')).unwrap());
+
+        chunker.chunks_into(5, None, &mut chunks).unwrap();
+        assert_eq!(chunks, chunker.chunks(5, None).unwrap());
+        assert_eq!(chunks.capacity(), 16);
+    }
+
+    #[test]
+    fn chunks_with_hashes() {
+        struct Fnv1a(u64);
+
+        impl Default for Fnv1a {
+            fn default() -> Self {
+                Fnv1a(0xcbf29ce484222325)
+            }
+        }
+
+        impl Hasher for Fnv1a {
+            fn write(&mut self, bytes: &[u8]) {
+                for &b in bytes {
+                    self.0 = (self.0 ^ b as u64).wrapping_mul(0x100000001b3);
+                }
+            }
+
+            fn finish(&self) -> u64 {
+                self.0
+            }
+        }
+
+        let log = "aaaa";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_with_hashes::<Fnv1a>(4, None).unwrap();
+        assert_eq!(chunks.len(), 4);
+        for (chunk, hash) in chunks {
+            assert_eq!(chunk, b"a");
+            assert_eq!(hash, 0xaf63dc4c8601ec8c);
+        }
+    }
+
+    #[test]
+    fn chunks_per_records() {
+        let log = HAPROXY_LOG;
+        let log_lines: Vec<_> = log.lines().collect();
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_per_records(3, '\n').unwrap();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(
+            String::from_utf8_lossy(chunks[0]),
+            format!("{}\n{}\n{}\n", log_lines[0], log_lines[1], log_lines[2])
+        );
+        assert_eq!(
+            String::from_utf8_lossy(chunks[3]),
+            format!("{}\n", log_lines[9])
+        );
+    }
+
+    #[test]
+    fn chunks_per_records_partial_tail() {
+        let log = "01\n23\n45";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_per_records(2, '\n').unwrap();
+        assert_eq!(chunks, vec![&b"01\n23\n"[..], b"45"]);
+        assert!(matches!(
+            chunker.chunks_per_records(0, '\n'),
+            Err(ChunkerError::InvalidChunkSize)
+        ));
+    }
+
+    #[test]
+    fn chunks_by_predicate() {
+        let log = "{\"a\": 1}\n{\"b\":\n 2}\n{\"c\": 3}\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .chunks_by(3, |data, i| {
+                i > 0 && data[i - 1] == b'}' && data[i] == b'\n'
+            })
+            .unwrap();
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(
+            String::from_utf8_lossy(chunks[0]),
+            "{\"a\": 1}\n{\"b\":\n 2}\n"
+        );
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "{\"c\": 3}\n");
+    }
+
+    #[test]
+    fn chunks_by_matches_delimiter() {
+        let log = HAPROXY_LOG;
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for count in 1..10 {
+            assert_eq!(
+                chunker
+                    .chunks_by(count, |data, i| data[i] == b'\n')
+                    .unwrap(),
+                chunker.chunks(count, Some('\n')).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn chunks_strict() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(
+            chunker.chunks_strict(2, '\n').unwrap(),
+            chunker.chunks(2, Some('\n')).unwrap()
+        );
+
+        let err = chunker.chunks_strict(4, ',').unwrap_err();
+        assert!(matches!(
+            err,
+            ChunkerError::InsufficientChunks {
+                requested: 4,
+                achieved: 1
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "requested 4 chunks, but only 1 could be produced"
+        );
+    }
+
+    #[test]
+    fn chunks_lines_mixed_terminators() {
+        let log = "01\r\n23\n45\r\n67\n89\r\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for count in 1..=log.len() {
+            let chunks = chunker.chunks_lines(count).unwrap();
+            assert_eq!(chunks.concat(), log.as_bytes());
+            for chunk in chunks {
+                assert!(chunk.ends_with(b"\n"));
+                assert!(!chunk.starts_with(b"\n"));
+            }
+        }
+        let chunks = chunker.chunks_lines(2).unwrap();
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\r\n23\n45\r\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89\r\n");
+    }
+
+    #[test]
+    fn estimate_chunk_count() {
+        let log = HAPROXY_LOG;
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(chunker.estimate_chunk_count(5, Some('\n')).unwrap(), 4);
+        for count in 1..20 {
+            for delimiter in [None, Some('\n')] {
+                assert_eq!(
+                    chunker.estimate_chunk_count(count, delimiter).unwrap(),
+                    chunker.chunks(count, delimiter).unwrap().len()
+                );
+            }
+        }
+        assert!(matches!(
+            chunker.estimate_chunk_count(0, None),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+
+    #[test]
+    fn lock_and_unlock() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+        file.rewind().unwrap();
+
+        let mut chunker = FileChunker::new(&file).unwrap();
+        #[cfg(unix)]
+        {
+            chunker.lock().unwrap();
+            assert_eq!(chunker.chunks(2, Some('\n')).unwrap().len(), 2);
+            chunker.unlock().unwrap();
+        }
+        #[cfg(not(unix))]
+        assert!(matches!(chunker.lock(), Err(ChunkerError::Io(_))));
+
+        let mut chunker = FileChunker::new_buffered(&file).unwrap();
+        assert!(matches!(chunker.lock(), Err(ChunkerError::Io(_))));
+    }
+
+    #[test]
+    fn split_to_files() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let chunker = FileChunker::new(&file).unwrap();
+        let paths = chunker
+            .split_to_files(2, Some('\n'), dir.path(), "part")
+            .unwrap();
+        assert_eq!(
+            paths,
+            vec![dir.path().join("part_000"), dir.path().join("part_001")]
+        );
+        assert_eq!(fs::read(&paths[0]).unwrap(), b"01\n23\n45\n");
+        assert_eq!(fs::read(&paths[1]).unwrap(), b"67\n89");
+    }
+
+    #[test]
+    fn chunks_merged() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_merged(3, None, 0.6).unwrap();
+        assert_eq!(chunks, vec![&b"0123"[..], b"456789"]);
+
+        let chunks = chunker.chunks_merged(3, None, 0.25).unwrap();
+        assert_eq!(chunks, chunker.chunks(3, None).unwrap());
+
+        let chunks = chunker.chunks_merged(1, None, 1.0).unwrap();
+        assert_eq!(chunks, vec![log.as_bytes()]);
+    }
+
+    #[test]
+    fn chunks_detailed() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_detailed(2, Some('\n')).unwrap();
+        assert_eq!(
+            chunks,
+            vec![
+                Chunk {
+                    bytes: b"01\n23\n45\n",
+                    offset: 0,
+                    ends_on_delimiter: true
+                },
+                Chunk {
+                    bytes: b"67\n89",
+                    offset: 9,
+                    ends_on_delimiter: false
+                },
+            ]
+        );
+
+        let chunks = chunker.chunks_detailed(2, None).unwrap();
+        assert!(chunks.iter().all(|chunk| !chunk.ends_on_delimiter));
+    }
+
+    #[test]
+    fn chunks_enumerated() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_enumerated(5, Some('\n')).unwrap();
+        assert_eq!(
+            chunks,
+            vec![(0, &b"01\n23\n"[..]), (1, b"45\n67\n"), (2, b"89"),]
+        );
+    }
+
+    #[test]
+    fn chunk_size_does_not_overflow() {
+        assert_eq!(chunk_size(usize::MAX, 1), usize::MAX);
+        assert_eq!(chunk_size(usize::MAX, 2), usize::MAX / 2 + 1);
+        assert_eq!(chunk_size(usize::MAX - 1, usize::MAX), 1);
+    }
+
+    #[test]
+    fn file_too_large() {
+        let size = u64::from(u32::MAX) + 1;
+        if usize::BITS < 64 {
+            assert!(matches!(
+                addressable_len(size),
+                Err(ChunkerError::FileTooLarge { size: s }) if s == size
+            ));
+        } else {
+            assert_eq!(addressable_len(size).unwrap() as u64, size);
+        }
+    }
+
+    #[test]
+    fn chunks_matching() {
+        let log = "ok 1\nerr 2\nok 3\nerr 4\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .chunks_matching(8, Some('\n'), |chunk| chunk.starts_with(b"err"))
+            .unwrap();
+        assert_eq!(chunks, vec![&b"err 2\n"[..], b"err 4\n"]);
+
+        let chunks = chunker.chunks_matching(8, Some('\n'), |_| true).unwrap();
+        assert_eq!(chunks, chunker.chunks(8, Some('\n')).unwrap());
+
+        assert!(matches!(
+            chunker.chunks_matching(0, None, |_| true),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+
+    #[test]
+    fn for_each_chunk() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let mut chunks = Vec::new();
+        chunker
+            .for_each_chunk(4, Some('\n'), |chunk| chunks.push(chunk.to_vec()))
+            .unwrap();
+        assert_eq!(chunks, chunker.chunks(4, Some('\n')).unwrap());
+
+        assert!(matches!(
+            chunker.for_each_chunk(0, None, |_| {}),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+
+    #[test]
+    fn chunks_per_cpu() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_per_cpu(Some('\n')).unwrap();
+        assert_eq!(chunks, chunker.chunks(cpu_count(), Some('\n')).unwrap());
+        assert!(chunks.len() <= cpu_count());
+    }
+
+    #[test]
+    fn chunks_repeated_calls_share_mapping() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let first = chunker.chunks(4, Some('\n')).unwrap();
+        let second = chunker.chunks(4, Some('\n')).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first[0].as_ptr(), second[0].as_ptr());
+
+        let other = chunker.chunks(2, None).unwrap();
+        assert_eq!(first[0].as_ptr(), other[0].as_ptr());
+    }
+
+    #[test]
+    fn remap() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(b"01\n23\n").unwrap();
+        file.flush().unwrap();
+
+        let mut chunker = FileChunker::new(&file).unwrap().with_len(3);
+        assert_eq!(chunker.chunks(1, None).unwrap(), vec![&b"01\n"[..]]);
+
+        file.write_all(b"45\n").unwrap();
+        file.flush().unwrap();
+        assert_eq!(chunker.len(), 3);
+
+        chunker.remap(&file).unwrap();
+        assert_eq!(chunker.len(), 9);
+        assert_eq!(
+            chunker.chunks(3, None).unwrap(),
+            vec![&b"01\n"[..], b"23\n", b"45\n"]
+        );
+    }
+
+    #[test]
+    fn chunks_reverse() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(
+            chunker.chunks_reverse(2, Some('\n')).unwrap(),
+            vec![&b"45\n67\n89"[..], b"01\n23\n"]
+        );
+        assert_eq!(
+            chunker.chunks_reverse(2, None).unwrap(),
+            vec![&b"5\n67\n89"[..], b"01\n23\n4"]
+        );
+        assert_eq!(
+            chunker.chunks_reverse(1, Some('\n')).unwrap(),
+            vec![log.as_bytes()]
+        );
+        assert!(matches!(
+            chunker.chunks_reverse(0, None),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+
+    #[test]
+    fn chunks_reverse_covers_file() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let mut chunks = chunker.chunks_reverse(4, Some('\n')).unwrap();
+        assert!(chunks.iter().all(|chunk| chunk.ends_with(b"\n")));
+        chunks.reverse();
+        assert_eq!(chunks.concat(), HAPROXY_LOG.as_bytes());
+    }
+
+    #[test]
+    fn debug_shows_len_only() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap().with_len(10);
+        assert_eq!(format!("{:?}", chunker), "FileChunker { len: 10 }");
+    }
+
+    #[test]
+    fn chunks_any_delimiter() {
+        let log = "01\n23\x0045\n67\x0089";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(
+            chunker.chunks_any_delimiter(14, b"\n\0").unwrap(),
+            vec![&b"01\n"[..], b"23\0", b"45\n", b"67\0", b"89"]
+        );
+        assert_eq!(
+            chunker.chunks_any_delimiter(2, b"\n").unwrap(),
+            chunker.chunks(2, Some('\n')).unwrap()
+        );
+        assert!(matches!(
+            chunker.chunks_any_delimiter(2, b""),
+            Err(ChunkerError::EmptyDelimiter)
+        ));
+    }
+
+    #[test]
+    fn verify_tiling() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(4, Some('\n')).unwrap();
+        assert!(chunker.verify_tiling(&chunks));
+        assert!(chunker.verify_tiling(&chunker.chunks_by_size(100, None).unwrap()));
+        assert!(chunker.verify_tiling(&chunker.chunks_lines(3).unwrap()));
+
+        assert!(!chunker.verify_tiling(&chunks[1..]));
+        assert!(!chunker.verify_tiling(&chunks[..chunks.len() - 1]));
+        assert!(!chunker.verify_tiling(&[chunks[1], chunks[0]]));
+        assert!(!chunker.verify_tiling(&[]));
+        assert!(!chunker.verify_tiling(&[HAPROXY_LOG.as_bytes()]));
+
+        let overlapping = chunker.chunks_overlapping(4, 10, Some('\n')).unwrap();
+        assert!(!chunker.verify_tiling(&overlapping));
+    }
+
+    #[test]
+    fn chunks_with_line_numbers() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(
+            chunker.chunks_with_line_numbers(3, '\n').unwrap(),
+            vec![(1, &b"01\n23\n"[..]), (3, b"45\n67\n"), (5, b"89")]
+        );
+        assert_eq!(
+            chunker.chunks_with_line_numbers(1, '\n').unwrap(),
+            vec![(1, log.as_bytes())]
+        );
+    }
+
+    #[test]
+    fn count_delimiter() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(chunker.count_delimiter(b'\n'), HAPROXY_LOG.lines().count());
+        assert_eq!(chunker.count_delimiter(b'\0'), 0);
+        assert_eq!(chunker.with_len(0).count_delimiter(b'\n'), 0);
+    }
+
+    #[test]
+    fn str_chunks() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(b"01\n23\n45\n\xff7\n89").unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.str_chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks, vec!["01\n23\n45\n", "\u{fffd}7\n89"]);
+        assert!(matches!(chunks[0], Cow::Borrowed(_)));
+        assert!(matches!(chunks[1], Cow::Owned(_)));
+    }
+
+    #[test]
+    fn chunks_owned() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let expected = chunker.chunks(4, Some('\n')).unwrap();
+        let chunks = chunker.chunks_owned(4, Some('\n')).unwrap();
+        assert_eq!(chunks, expected);
+        drop(chunker);
+
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| std::thread::spawn(move || chunk.len()))
+            .collect();
+        let total: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+        assert_eq!(total, HAPROXY_LOG.len());
+    }
+
+    #[test]
+    fn file_chunker_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<FileChunker>();
+    }
+
+    #[test]
+    fn chunks_in_scoped_threads() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks(4, Some('\n')).unwrap();
+        let total: usize = std::thread::scope(|scope| {
+            let handles: Vec<_> = chunks
+                .into_iter()
+                .map(|chunk| scope.spawn(move || chunk.len()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).sum()
+        });
+        assert_eq!(total, HAPROXY_LOG.len());
+    }
+
+    #[test]
+    fn from_reader() {
+        let log = "01\n23\n45\n67\n89";
+
+        let chunker = FileChunker::from_reader(log.as_bytes()).unwrap();
+        assert_eq!(chunker.len(), log.len());
+        let chunks = chunker.chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks, vec![&b"01\n23\n45\n"[..], b"67\n89"]);
+
+        let chunker = FileChunker::from_reader(std::io::empty()).unwrap();
+        assert!(chunker.is_empty());
+    }
+
+    #[test]
+    fn chunks_aligned() {
+        let log = "aaaabbbbccccddddeeee";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(
+            chunker.chunks_aligned(2, 4).unwrap(),
+            vec![&b"aaaabbbb"[..], b"ccccddddeeee"]
+        );
+        assert_eq!(
+            chunker.chunks_aligned(3, 4).unwrap(),
+            vec![&b"aaaa"[..], b"bbbbcccc", b"ddddeeee"]
+        );
+        assert_eq!(
+            chunker.chunks_aligned(20, 4).unwrap(),
+            vec![&b"aaaa"[..], b"bbbb", b"cccc", b"dddd", b"eeee"]
+        );
+        assert_eq!(chunker.chunks_aligned(1, 4).unwrap(), vec![log.as_bytes()]);
+        assert!(matches!(
+            chunker.chunks_aligned(2, 0),
+            Err(ChunkerError::InvalidChunkSize)
+        ));
+        assert!(matches!(
+            chunker.chunks_aligned(0, 4),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+        assert_eq!(
+            chunker.chunks_aligned(2, 3).unwrap(),
+            vec![&b"aaaabbbbc"[..], b"cccddddeeee"]
+        );
+        for count in 1..=log.len() + 1 {
+            for record_size in 1..=log.len() + 1 {
+                let chunks = chunker.chunks_aligned(count, record_size).unwrap();
+                assert!(chunks.len() <= count);
+                assert_eq!(chunks.concat(), log.as_bytes());
+                let (_, rest) = chunks.split_last().unwrap();
+                assert!(rest.iter().all(|chunk| chunk.len() % record_size == 0));
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_aligned_strict() {
+        let log = "aaaabbbbccccddddeeee";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(
+            chunker.chunks_aligned_strict(2, 4).unwrap(),
+            chunker.chunks_aligned(2, 4).unwrap()
+        );
+        assert!(matches!(
+            chunker.chunks_aligned_strict(2, 3),
+            Err(ChunkerError::UnalignedLength {
+                len: 20,
+                record_size: 3
+            })
+        ));
+        assert!(matches!(
+            chunker.chunks_aligned_strict(2, 0),
+            Err(ChunkerError::InvalidChunkSize)
+        ));
+    }
+
+    #[test]
+    fn chunk_stats() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(
+            chunker.chunk_stats(3, Some('\n')).unwrap(),
+            ChunkStats {
+                count: 3,
+                min_len: 2,
+                max_len: 6,
+                mean_len: 14.0 / 3.0,
+                last_ends_on_delimiter: false,
+            }
+        );
+        let stats = chunker.with_len(12).chunk_stats(2, Some('\n')).unwrap();
+        assert_eq!(stats.count, 2);
+        assert!(stats.last_ends_on_delimiter);
+
+        let empty: File = tempfile::tempfile().unwrap();
+        let chunker = FileChunker::new(&empty).unwrap();
+        assert_eq!(
+            chunker.chunk_stats(3, Some('\n')).unwrap(),
+            ChunkStats {
+                count: 0,
+                min_len: 0,
+                max_len: 0,
+                mean_len: 0.0,
+                last_ends_on_delimiter: false,
+            }
+        );
+    }
+
+    #[test]
+    fn for_each_bounded() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let seen = Mutex::new(Vec::new());
+        chunker
+            .for_each_bounded(10, Some('\n'), 2, |chunk| {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(current, Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(5));
+                seen.lock().unwrap().push(chunk.to_vec());
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            })
+            .unwrap();
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+        let mut seen = seen.into_inner().unwrap();
+        let mut expected = chunker.chunks(10, Some('\n')).unwrap();
+        seen.sort();
+        expected.sort();
+        assert_eq!(seen, expected);
+
+        assert!(matches!(
+            chunker.for_each_bounded(0, None, 2, |_| {}),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+
+    #[test]
+    fn chunks_u8() {
+        let log = b"01\xff23\xff45\xff67\xff89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(
+            chunker.chunks_u8(2, Some(0xff)).unwrap(),
+            vec![&b"01\xff23\xff45\xff"[..], b"67\xff89"]
+        );
+        assert_eq!(
+            chunker.chunks_u8(3, None).unwrap(),
+            chunker.chunks(3, None).unwrap()
+        );
+        assert!(matches!(
+            chunker.chunks_u8(0, None),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+
+    #[test]
+    fn chunks_implicit_final() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_implicit_final(2, '\n').unwrap();
+        assert_eq!(chunks, vec![&b"01\n23\n45\n"[..], b"67\n89\n"]);
+        assert!(matches!(chunks[0], Cow::Borrowed(_)));
+        assert!(matches!(chunks[1], Cow::Owned(_)));
+
+        let chunker = chunker.with_len(12);
+        let chunks = chunker.chunks_implicit_final(2, '\n').unwrap();
+        assert_eq!(chunks, vec![&b"01\n23\n45\n"[..], b"67\n"]);
+        assert!(chunks.iter().all(|chunk| matches!(chunk, Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn nth_delimiter() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(chunker.nth_delimiter(b'\n', 0), Some(2));
+        assert_eq!(chunker.nth_delimiter(b'\n', 3), Some(11));
+        assert_eq!(chunker.nth_delimiter(b'\n', 4), None);
+        assert_eq!(chunker.nth_delimiter(b'\0', 0), None);
+    }
+
+    #[test]
+    fn chunks_at() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(
+            chunker.chunks_at(&[3, 9]).unwrap(),
+            vec![&b"01\n"[..], b"23\n45\n", b"67\n89"]
+        );
+        assert_eq!(chunker.chunks_at(&[]).unwrap(), vec![log.as_bytes()]);
+        assert_eq!(
+            chunker.chunks_at(&[0, 14]).unwrap(),
+            vec![&b""[..], log.as_bytes(), b""]
+        );
+
+        let starts: Vec<usize> = chunker
+            .boundaries(3, Some('\n'))
+            .unwrap()
+            .into_iter()
+            .skip(1)
+            .map(|range| range.start)
+            .collect();
+        assert_eq!(
+            chunker.chunks_at(&starts).unwrap(),
+            chunker.chunks(3, Some('\n')).unwrap()
+        );
+
+        assert!(matches!(
+            chunker.chunks_at(&[9, 3]),
+            Err(ChunkerError::InvalidRange(range)) if range.start == 9 && range.end == 3
+        ));
+        assert!(matches!(
+            chunker.chunks_at(&[3, 15]),
+            Err(ChunkerError::InvalidRange(range)) if range == (3..15)
+        ));
+    }
+
+    #[test]
+    fn chunks_with_progress() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let mut offsets = Vec::new();
+        let chunks = chunker
+            .chunks_with_progress(3, Some('\n'), |offset| offsets.push(offset))
+            .unwrap();
+        assert_eq!(chunks, chunker.chunks(3, Some('\n')).unwrap());
+        assert_eq!(offsets, vec![6, 12, 14]);
     }
 
-    /// Divide the file into chunks approximately equal size. Returns a vector of memory-mapped
-    /// slices that each correspond to a chunk.
-    ///
-    /// If a delimeter is provided, then each chunk will end with an instance of the delimeter,
-    /// assuming the delimiter exists in the file. This is useful when working with text files
-    /// that have newline characters, for example. If no delimeter is provided, then each chunk
-    /// will be the same size, except for the last chunk which may be smaller.
-    ///
-    /// It is assumed that the underlying `File` will not change while this function is running.
-    pub fn chunks(&self, count: usize, delimiter: Option<char>) -> Result<Vec<&[u8]>> {
-        let chunk_size = chunk_size(self.mmap.len(), count);
-        let mut chunks = Vec::new();
-        let mut offset = 0;
-        while offset < self.mmap.len() {
-            let mut chunk_end = offset + chunk_size;
-            if let Some(delimiter) = delimiter {
-                while (chunk_end < self.mmap.len() - 1) && (self.mmap[chunk_end] != delimiter as u8)
-                {
-                    chunk_end += 1;
-                }
-                chunk_end += 1;
-            }
-            if chunk_end > self.mmap.len() {
-                chunks.push(&self.mmap[offset..]);
-                break;
-            }
-            chunks.push(&self.mmap[offset..chunk_end]);
-            offset = chunk_end;
-        }
+    #[test]
+    fn try_new() {
+        let empty: File = tempfile::tempfile().unwrap();
+        assert!(FileChunker::new(&empty).is_ok());
+        assert!(matches!(
+            FileChunker::try_new(&empty),
+            Err(ChunkerError::EmptyFile)
+        ));
 
-        Ok(chunks)
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+        assert_eq!(
+            FileChunker::try_new(&file).unwrap().len(),
+            HAPROXY_LOG.len()
+        );
     }
-}
 
-fn chunk_size(file_size: usize, count: usize) -> usize {
-    f64::ceil(file_size as f64 / count as f64) as usize
-}
+    #[test]
+    fn chunks_cancellable() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::io::Write;
+        let chunker = FileChunker::new(&file).unwrap();
+        let cancel = AtomicBool::new(false);
+        assert_eq!(
+            chunker.chunks_cancellable(4, Some('\n'), &cancel).unwrap(),
+            chunker.chunks(4, Some('\n')).unwrap()
+        );
+
+        cancel.store(true, Ordering::Relaxed);
+        assert!(matches!(
+            chunker.chunks_cancellable(4, Some('\n'), &cancel),
+            Err(ChunkerError::Cancelled)
+        ));
+    }
 
     #[test]
-    fn chunks_with_delimiter() {
+    fn chunks_split_first() {
         let log = "01\n23\n45\n67\n89";
 
         let mut file: File = tempfile::tempfile().unwrap();
@@ -87,206 +3410,464 @@ mod test {
         file.flush().unwrap();
 
         let chunker = FileChunker::new(&file).unwrap();
-        let chunks = chunker.chunks(2, Some('\n')).unwrap();
-        assert_eq!(chunks.len(), 2);
-        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
-        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n45\n");
-        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89");
+        assert_eq!(
+            chunker.chunks_split_first(2, '\n').unwrap(),
+            vec![(&b"01\n"[..], &b"23\n45\n"[..]), (&b"67\n"[..], &b"89"[..]),]
+        );
+        assert_eq!(
+            chunker.with_len(2).chunks_split_first(1, '\n').unwrap(),
+            vec![(&b"01"[..], &b""[..])]
+        );
     }
 
     #[test]
-    fn chunks_without_delimiter() {
-        let log = "0123456789";
+    fn chunks_text_utf8_bom() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(b"\xef\xbb\xbf01\n23\n45\n67\n89").unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert_eq!(
+            chunker.chunks_text(2, '\n').unwrap(),
+            vec![&b"01\n23\n45\n"[..], b"67\n89"]
+        );
+    }
 
+    #[test]
+    fn chunks_text_utf16_bom() {
+        // U+0A30 U+4100 contains the bytes 0x0A 0x00 across a code unit boundary, which must not
+        // be mistaken for a little-endian newline.
+        let text = "01\n\u{0a30}\u{4100}\n45\n67\n89";
+        let utf16: Vec<u16> = text.encode_utf16().collect();
+
+        let le: Vec<u8> = utf16.iter().flat_map(|unit| unit.to_le_bytes()).collect();
         let mut file: File = tempfile::tempfile().unwrap();
-        file.write_all(log.as_bytes()).unwrap();
+        file.write_all(b"\xff\xfe").unwrap();
+        file.write_all(&le).unwrap();
         file.flush().unwrap();
 
         let chunker = FileChunker::new(&file).unwrap();
-        let chunks = chunker.chunks(10, None).unwrap();
-        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
-        assert_eq!(chunks.len(), 10);
-        (0..9).into_iter().for_each(|i| {
-            assert_eq!(String::from_utf8_lossy(chunks[i]), format!("{}", i));
+        let chunks = chunker.chunks_text(5, '\n').unwrap();
+        assert_eq!(chunks.concat(), le);
+        let decoded: Vec<String> = chunks
+            .iter()
+            .map(|chunk| {
+                let units: Vec<u16> = chunk
+                    .chunks_exact(2)
+                    .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                    .collect();
+                String::from_utf16(&units).unwrap()
+            })
+            .collect();
+        assert_eq!(decoded, vec!["01\n\u{0a30}\u{4100}\n", "45\n67\n", "89"]);
+
+        let be: Vec<u8> = utf16.iter().flat_map(|unit| unit.to_be_bytes()).collect();
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(b"\xfe\xff").unwrap();
+        file.write_all(&be).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.chunks_text(5, '\n').unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[..2].iter().all(|chunk| chunk.ends_with(b"\0\n")));
+    }
+
+    #[test]
+    fn chunks_utf16() {
+        // U+0A30 U+4100 contains the bytes 0x0A 0x00 across a code unit boundary in
+        // little-endian order, and U+300A U+0041 does the same for 0x00 0x0A in big-endian order.
+        let text = "01\n\u{0a30}\u{4100}\u{300a}\u{0041}\n45\n67\n89";
+        let utf16: Vec<u16> = text.encode_utf16().collect();
+
+        for (endian, encode, decode) in [
+            (
+                Endian::Little,
+                u16::to_le_bytes as fn(u16) -> [u8; 2],
+                u16::from_le_bytes as fn([u8; 2]) -> u16,
+            ),
+            (Endian::Big, u16::to_be_bytes, u16::from_be_bytes),
+        ] {
+            let bytes: Vec<u8> = utf16.iter().flat_map(|&unit| encode(unit)).collect();
+            let mut file: File = tempfile::tempfile().unwrap();
+            file.write_all(&bytes).unwrap();
+            file.flush().unwrap();
+
+            let chunker = FileChunker::new(&file).unwrap();
+            let chunks = chunker.chunks_utf16(5, endian).unwrap();
+            assert_eq!(chunks.concat(), bytes);
+            let decoded: Vec<String> = chunks
+                .iter()
+                .map(|chunk| {
+                    let units: Vec<u16> = chunk
+                        .chunks_exact(2)
+                        .map(|unit| decode([unit[0], unit[1]]))
+                        .collect();
+                    String::from_utf16(&units).unwrap()
+                })
+                .collect();
+            assert_eq!(
+                decoded,
+                vec!["01\n\u{0a30}\u{4100}\u{300a}\u{0041}\n", "45\n67\n", "89"]
+            );
+        }
+    }
+
+    #[test]
+    fn new_with_retries() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new_with_retries(&file, 3).unwrap();
+        assert_eq!(chunker.as_bytes(), HAPROXY_LOG.as_bytes());
+    }
+
+    #[test]
+    fn with_retries_retries_transient_errors() {
+        let mut attempts = 0;
+        let result = with_retries(3, || {
+            attempts += 1;
+            match attempts {
+                1 => Err(io::Error::from(io::ErrorKind::Interrupted)),
+                2 => Err(io::Error::from(io::ErrorKind::WouldBlock)),
+                _ => Ok(attempts),
+            }
         });
+        assert_eq!(result.unwrap(), 3);
+
+        let mut attempts = 0;
+        let result: io::Result<()> = with_retries(2, || {
+            attempts += 1;
+            Err(io::Error::from(io::ErrorKind::Interrupted))
+        });
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Interrupted);
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let result: io::Result<()> = with_retries(2, || {
+            attempts += 1;
+            Err(io::Error::from(io::ErrorKind::PermissionDenied))
+        });
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(attempts, 1);
     }
 
     #[test]
-    fn chunks_with_delimiter_empty() {
-        let log = "";
+    fn detect_delimiter() {
+        for (contents, expected) in [
+            (&b"01\n23\n45"[..], Some(b'\n')),
+            (b"01\r\n23\r\n45", Some(b'\n')),
+            (b"01\x0023\x0045\n", Some(b'\0')),
+            (b"01\r23\r45", Some(b'\r')),
+            (b"012345", None),
+            (b"", None),
+        ] {
+            let mut file: File = tempfile::tempfile().unwrap();
+            file.write_all(contents).unwrap();
+            file.flush().unwrap();
+
+            let chunker = FileChunker::new(&file).unwrap();
+            assert_eq!(chunker.detect_delimiter(), expected);
+        }
 
+        // Only the start of the file is sampled.
         let mut file: File = tempfile::tempfile().unwrap();
-        file.write_all(log.as_bytes()).unwrap();
+        file.write_all(&[b'\n'; 8 * 1024]).unwrap();
+        file.write_all(&[b'\0'; 16 * 1024]).unwrap();
         file.flush().unwrap();
 
         let chunker = FileChunker::new(&file).unwrap();
-        let chunks = chunker.chunks(2, Some('\n')).unwrap();
-        assert_eq!(chunks.len(), 0);
-        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(chunker.detect_delimiter(), Some(b'\n'));
     }
 
     #[test]
-    fn chunks_without_delimiter_empty() {
-        let log = "";
-
+    fn chunks_text_without_bom() {
         let mut file: File = tempfile::tempfile().unwrap();
-        file.write_all(log.as_bytes()).unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
         file.flush().unwrap();
 
         let chunker = FileChunker::new(&file).unwrap();
-        let chunks = chunker.chunks(2, None).unwrap();
-        assert_eq!(chunks.len(), 0);
-        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(
+            chunker.chunks_text(4, '\n').unwrap(),
+            chunker.chunks(4, Some('\n')).unwrap()
+        );
     }
 
     #[test]
-    fn chunks_with_delimiter_start_and_end() {
-        let log = "\n01\n23\n45\n67\n89\n";
+    fn coalesce() {
+        let log = "01\n23\n45\n67\n89";
 
         let mut file: File = tempfile::tempfile().unwrap();
         file.write_all(log.as_bytes()).unwrap();
         file.flush().unwrap();
 
         let chunker = FileChunker::new(&file).unwrap();
-        let chunks = chunker.chunks(2, Some('\n')).unwrap();
-        assert_eq!(chunks.len(), 2);
-        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
-        assert_eq!(String::from_utf8_lossy(chunks[0]), "\n01\n23\n45\n");
-        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89\n");
+        let chunks = chunker.chunks(log.len(), Some('\n')).unwrap();
+        assert_eq!(
+            chunker.coalesce(&chunks, 2).unwrap(),
+            vec![&b"01\n23\n"[..], b"45\n67\n", b"89"]
+        );
+        assert_eq!(chunker.coalesce(&chunks, 5).unwrap(), vec![log.as_bytes()]);
+        assert_eq!(chunker.coalesce(&chunks, 1).unwrap(), chunks);
+        assert!(chunker.coalesce(&[], 2).unwrap().is_empty());
+
+        assert!(matches!(
+            chunker.coalesce(&chunks, 0),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+        assert!(matches!(
+            chunker.coalesce(&[chunks[0], chunks[2]], 2),
+            Err(ChunkerError::NonContiguousChunks)
+        ));
+        assert!(matches!(
+            chunker.coalesce(&[chunks[1], chunks[0]], 2),
+            Err(ChunkerError::NonContiguousChunks)
+        ));
+        assert!(matches!(
+            chunker.coalesce(&[b"01\n"], 2),
+            Err(ChunkerError::NonContiguousChunks)
+        ));
+    }
+
+    fn bytes_with_delimiters() -> impl Strategy<Value = u8> {
+        prop_oneof![Just(b'\n'), any::<u8>()]
+    }
+
+    proptest! {
+        #[test]
+        fn chunks_always_tile(
+            data in prop::collection::vec(bytes_with_delimiters(), 0..64),
+            count in 1usize..80,
+            delimiter in prop::option::of(bytes_with_delimiters()),
+        ) {
+            let chunker = FileChunker::from_reader(&data[..]).unwrap();
+            let chunks = chunker.chunks(count, delimiter.map(char::from)).unwrap();
+            prop_assert!(chunker.verify_tiling(&chunks));
+            prop_assert!(chunks.len() <= count);
+            prop_assert!(chunks.iter().all(|chunk| !chunk.is_empty()));
+            if let Some(delimiter) = delimiter {
+                for chunk in &chunks[..chunks.len().saturating_sub(1)] {
+                    prop_assert_eq!(chunk.last(), Some(&delimiter));
+                }
+            }
+        }
+
+        #[test]
+        fn chunks_by_size_always_tile(
+            data in prop::collection::vec(bytes_with_delimiters(), 0..64),
+            max_bytes in 1usize..80,
+            delimiter in prop::option::of(bytes_with_delimiters()),
+        ) {
+            let chunker = FileChunker::from_reader(&data[..]).unwrap();
+            let chunks = chunker.chunks_by_size(max_bytes, delimiter.map(char::from)).unwrap();
+            prop_assert!(chunker.verify_tiling(&chunks));
+        }
+
+        #[test]
+        fn chunks_reject_zero_count(data in prop::collection::vec(any::<u8>(), 0..16)) {
+            let chunker = FileChunker::from_reader(&data[..]).unwrap();
+            prop_assert!(matches!(
+                chunker.chunks(0, Some('\n')),
+                Err(ChunkerError::InvalidChunkCount)
+            ));
+        }
     }
 
     #[test]
-    fn chunks_with_delimiter_only() {
-        let log = "\n\n\n\n\n\n\n\n\n\n";
+    fn chunks_framed() {
+        let log = "<01><2<3>4><56><7<8<9>>>";
 
         let mut file: File = tempfile::tempfile().unwrap();
         file.write_all(log.as_bytes()).unwrap();
         file.flush().unwrap();
 
         let chunker = FileChunker::new(&file).unwrap();
-        let chunks = chunker.chunks(2, Some('\n')).unwrap();
-        assert_eq!(chunks.len(), 2);
-        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
-        assert_eq!(String::from_utf8_lossy(chunks[0]), "\n\n\n\n\n\n");
-        assert_eq!(String::from_utf8_lossy(chunks[1]), "\n\n\n\n");
+        assert_eq!(
+            chunker.chunks_framed(log.len(), b'<', b'>').unwrap(),
+            vec![&b"<01>"[..], b"<2<3>4>", b"<56>", b"<7<8<9>>>"]
+        );
+        assert_eq!(
+            chunker.chunks_framed(2, b'<', b'>').unwrap(),
+            vec![&b"<01><2<3>4><56>"[..], b"<7<8<9>>>"]
+        );
+        assert!(matches!(
+            chunker.chunks_framed(0, b'<', b'>'),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
     }
 
     #[test]
-    fn chunks_with_nonexistent_delimiter() {
-        let log = "0123456789";
+    fn chunks_framed_same_open_and_close() {
+        let log = "'a>b''c''d'";
 
         let mut file: File = tempfile::tempfile().unwrap();
         file.write_all(log.as_bytes()).unwrap();
         file.flush().unwrap();
 
         let chunker = FileChunker::new(&file).unwrap();
-        let chunks = chunker.chunks(10, Some('\n')).unwrap();
-        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
-        assert_eq!(chunks.len(), 1);
-        assert_eq!(chunks[0], b"0123456789");
+        assert_eq!(
+            chunker.chunks_framed(log.len(), b'\'', b'\'').unwrap(),
+            vec![&b"'a>b'"[..], b"'c'", b"'d'"]
+        );
     }
 
     #[test]
-    fn chunks_with_delimiter_long_text() {
-        let log = "Nov 23 06:26:40 ip-10-1-1-1 haproxy[20128]: 10.1.1.10:57305 [23/Nov/2019:06:26:40.781] public myapp/i-05fa49c0e7db8c328 0/0/0/78/78 206 913/458 - - ---- 9/9/6/0/0 0/0 {bytes=0-0} {||1|bytes 0-0/499704} \"GET /2518cb13a48bdf53b2f936f44e7042a3cc7baa06 HTTP/1.1\"
-Nov 23 06:26:41 ip-10-1-1-1 haproxy[20128]: 10.1.1.11:51819 [23/Nov/2019:06:27:41.780] public myapp/i-059c225b48702964a 0/0/0/80/80 200 802/142190 - - ---- 8/8/5/0/0 0/0 {} {||141752|} \"GET /2043f2eb9e2691edcc0c8084d1ffce8bd70bc6e7 HTTP/1.1\"
-Nov 23 06:26:42 ip-10-1-1-1 haproxy[20128]: 10.1.1.12:38870 [23/Nov/2019:06:28:42.773] public myapp/i-048088fd46abe7ed0 0/0/0/77/100 200 823/512174 - - ---- 8/8/5/0/0 0/0 {} {||511736|} \"GET /eb59c0b5dad36f080f3d261c6257ce0e21ef1a01 HTTP/1.1\"
-Nov 23 06:26:43 ip-10-1-1-1 haproxy[20128]: 10.1.1.13:35528 [23/Nov/2019:06:29:43.775] public myapp/i-05e9315b035d50f62 0/0/0/103/105 200 869/431481 - - ---- 8/8/1/0/0 0/0 {} {|||} \"GET /164672c9d75c76a8fa237c24f9cbfd2222554f6d HTTP/1.1\"
-Nov 23 06:26:44 ip-10-1-1-1 haproxy[20128]: 10.1.1.14:48553 [23/Nov/2019:06:30:44.808] public myapp/i-0008bfe6b1c98e964 0/0/0/72/73 200 840/265518 - - ---- 7/7/5/0/0 0/0 {} {||265080|} \"GET /e3b526928196d19ab3419d433f3de0ceb71e62b5 HTTP/1.1\"
-Nov 23 06:26:45 ip-10-1-1-1 haproxy[20128]: 10.1.1.15:60969 [23/Nov/2019:06:31:45.727] public myapp/i-005a2bfdba4c405a8 0/0/0/146/167 200 852/304622 - - ---- 7/7/5/0/0 0/0 {} {||304184|} \"GET /52f5edb4a46276defe54ead2fae3a19fb8cafdb6 HTTP/1.1\"
-Nov 23 06:26:46 ip-10-1-1-1 haproxy[20128]: 10.1.1.14:48539 [23/Nov/2019:06:32:46.730] public myapp/i-03b180605be4fa176 0/0/0/171/171 200 889/124142 - - ---- 6/6/4/0/0 0/0 {} {||123704|} \"GET /ef9e0c85cc1c76d7dc777f5b19d7cb85478496e4 HTTP/1.1\"
-Nov 23 06:26:47 ip-10-1-1-1 haproxy[20128]: 10.1.1.11:51847 [23/Nov/2019:06:33:47.886] public myapp/i-0aa566420409956d6 0/0/0/28/28 206 867/458 - - ---- 6/6/4/0/0 0/0 {bytes=0-0} {} \"GET /3c7ace8c683adcad375a4d14995734ac0db08bb3 HTTP/1.1\"
-Nov 23 06:26:48 ip-10-1-1-1 haproxy[20128]: 10.1.1.13:35554 [23/Nov/2019:06:34:48.866] public myapp/i-07f4205f35b4774b6 0/0/0/23/49 200 816/319662 - - ---- 5/5/3/0/0 0/0 {} {||319224|} \"GET /b95db0578977cd32658fa28b386c0db67ab23ee7 HTTP/1.1\"
-Nov 23 06:26:49 ip-10-1-1-1 haproxy[20128]: 10.1.1.12:38899 [23/Nov/2019:06:35:49.879] public myapp/i-08cb5309afd22e8c0 0/0/0/59/59 200 1000/112110 - - ---- 5/5/3/0/0 0/0 {} {||111672|} \"GET /5314ca870ed0f5e48a71adca185e4ff7f1d9d80f HTTP/1.1\"
-";
-        let log_lines: Vec<_> = log.lines().collect();
+    fn chunks_escaped() {
+        let log = "a,\"x\ny\"\nb,\"\"\"q\n\"\"\"\nc\n";
 
         let mut file: File = tempfile::tempfile().unwrap();
         file.write_all(log.as_bytes()).unwrap();
         file.flush().unwrap();
 
         let chunker = FileChunker::new(&file).unwrap();
-        let chunks = chunker.chunks(5, Some('\n')).unwrap();
-        assert_eq!(chunks.len(), 4); // N.B.: This is smaller than requested because we're chunking based on the delimeter
-        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
-        assert_eq!(
-            String::from_utf8_lossy(chunks[0]),
-            format!("{}\n{}\n", log_lines[0], &log_lines[1])
-        );
-        assert_eq!(
-            String::from_utf8_lossy(chunks[1]),
-            format!("{}\n{}\n{}\n", log_lines[2], &log_lines[3], &log_lines[4])
-        );
         assert_eq!(
-            String::from_utf8_lossy(chunks[2]),
-            format!("{}\n{}\n{}\n", log_lines[5], &log_lines[6], &log_lines[7])
+            chunker.chunks_escaped(log.len(), b'\n', b'"').unwrap(),
+            vec![&b"a,\"x\ny\"\n"[..], b"b,\"\"\"q\n\"\"\"\n", b"c\n"]
         );
         assert_eq!(
-            String::from_utf8_lossy(chunks[3]),
-            format!("{}\n{}\n", log_lines[8], &log_lines[9])
+            chunker.chunks_escaped(1, b'\n', b'"').unwrap(),
+            vec![log.as_bytes()]
         );
     }
 
     #[test]
-    fn chunks_without_delimiter_long_text() {
-        let log = "Nov 23 06:26:40 ip-10-1-1-1 haproxy[20128]: 10.1.1.10:57305 [23/Nov/2019:06:26:40.781] public myapp/i-05fa49c0e7db8c328 0/0/0/78/78 206 913/458 - - ---- 9/9/6/0/0 0/0 {bytes=0-0} {||1|bytes 0-0/499704} \"GET /2518cb13a48bdf53b2f936f44e7042a3cc7baa06 HTTP/1.1\"
-Nov 23 06:26:41 ip-10-1-1-1 haproxy[20128]: 10.1.1.11:51819 [23/Nov/2019:06:27:41.780] public myapp/i-059c225b48702964a 0/0/0/80/80 200 802/142190 - - ---- 8/8/5/0/0 0/0 {} {||141752|} \"GET /2043f2eb9e2691edcc0c8084d1ffce8bd70bc6e7 HTTP/1.1\"
-Nov 23 06:26:42 ip-10-1-1-1 haproxy[20128]: 10.1.1.12:38870 [23/Nov/2019:06:28:42.773] public myapp/i-048088fd46abe7ed0 0/0/0/77/100 200 823/512174 - - ---- 8/8/5/0/0 0/0 {} {||511736|} \"GET /eb59c0b5dad36f080f3d261c6257ce0e21ef1a01 HTTP/1.1\"
-Nov 23 06:26:43 ip-10-1-1-1 haproxy[20128]: 10.1.1.13:35528 [23/Nov/2019:06:29:43.775] public myapp/i-05e9315b035d50f62 0/0/0/103/105 200 869/431481 - - ---- 8/8/1/0/0 0/0 {} {|||} \"GET /164672c9d75c76a8fa237c24f9cbfd2222554f6d HTTP/1.1\"
-Nov 23 06:26:44 ip-10-1-1-1 haproxy[20128]: 10.1.1.14:48553 [23/Nov/2019:06:30:44.808] public myapp/i-0008bfe6b1c98e964 0/0/0/72/73 200 840/265518 - - ---- 7/7/5/0/0 0/0 {} {||265080|} \"GET /e3b526928196d19ab3419d433f3de0ceb71e62b5 HTTP/1.1\"
-Nov 23 06:26:45 ip-10-1-1-1 haproxy[20128]: 10.1.1.15:60969 [23/Nov/2019:06:31:45.727] public myapp/i-005a2bfdba4c405a8 0/0/0/146/167 200 852/304622 - - ---- 7/7/5/0/0 0/0 {} {||304184|} \"GET /52f5edb4a46276defe54ead2fae3a19fb8cafdb6 HTTP/1.1\"
-Nov 23 06:26:46 ip-10-1-1-1 haproxy[20128]: 10.1.1.14:48539 [23/Nov/2019:06:32:46.730] public myapp/i-03b180605be4fa176 0/0/0/171/171 200 889/124142 - - ---- 6/6/4/0/0 0/0 {} {||123704|} \"GET /ef9e0c85cc1c76d7dc777f5b19d7cb85478496e4 HTTP/1.1\"
-Nov 23 06:26:47 ip-10-1-1-1 haproxy[20128]: 10.1.1.11:51847 [23/Nov/2019:06:33:47.886] public myapp/i-0aa566420409956d6 0/0/0/28/28 206 867/458 - - ---- 6/6/4/0/0 0/0 {bytes=0-0} {} \"GET /3c7ace8c683adcad375a4d14995734ac0db08bb3 HTTP/1.1\"
-Nov 23 06:26:48 ip-10-1-1-1 haproxy[20128]: 10.1.1.13:35554 [23/Nov/2019:06:34:48.866] public myapp/i-07f4205f35b4774b6 0/0/0/23/49 200 816/319662 - - ---- 5/5/3/0/0 0/0 {} {||319224|} \"GET /b95db0578977cd32658fa28b386c0db67ab23ee7 HTTP/1.1\"
-Nov 23 06:26:49 ip-10-1-1-1 haproxy[20128]: 10.1.1.12:38899 [23/Nov/2019:06:35:49.879] public myapp/i-08cb5309afd22e8c0 0/0/0/59/59 200 1000/112110 - - ---- 5/5/3/0/0 0/0 {} {||111672|} \"GET /5314ca870ed0f5e48a71adca185e4ff7f1d9d80f HTTP/1.1\"
-";
+    fn chunks_with_capacity() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for count in [1, 4, 64, 1_000_000] {
+            let chunks = chunker.chunks(count, Some('\n')).unwrap();
+            assert!(chunks.capacity() <= count);
+            let presized = chunker
+                .chunks_with_capacity(count, Some('\n'), 100)
+                .unwrap();
+            assert!(presized.capacity() >= 100);
+            assert_eq!(presized, chunks);
+        }
+    }
+
+    #[test]
+    fn tail() {
+        let log = "01\n23\n45\n67\n89";
 
         let mut file: File = tempfile::tempfile().unwrap();
         file.write_all(log.as_bytes()).unwrap();
         file.flush().unwrap();
 
-        let chunks = 5;
-        let chunk_size = log.len() / chunks;
         let chunker = FileChunker::new(&file).unwrap();
-        let chunks = chunker.chunks(chunks, None).unwrap();
-
-        assert_eq!(chunks.len(), 5);
-        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), log.len());
+        assert_eq!(chunker.tail(4, Some('\n')).unwrap(), b"67\n89");
+        assert_eq!(chunker.tail(4, None).unwrap(), b"7\n89");
+        assert_eq!(chunker.tail(100, Some('\n')).unwrap(), log.as_bytes());
         assert_eq!(
-            String::from_utf8_lossy(chunks[0]),
-            log[(0 * chunk_size)..=(1 * chunk_size)].to_string()
+            chunker.tail(7, Some('\n')).unwrap(),
+            chunker.chunks_reverse(2, Some('\n')).unwrap()[0]
         );
+        assert!(matches!(
+            chunker.tail(0, None),
+            Err(ChunkerError::InvalidChunkSize)
+        ));
+    }
+
+    #[test]
+    fn nth_chunk() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for delimiter in [None, Some('\n')] {
+            let chunks = chunker.chunks(7, delimiter).unwrap();
+            for index in 0..chunks.len() + 2 {
+                assert_eq!(
+                    chunker.nth_chunk(index, 7, delimiter).unwrap(),
+                    chunks.get(index).copied()
+                );
+            }
+        }
+        assert_eq!(chunker.nth_chunk(usize::MAX, 7, None).unwrap(), None);
+        assert!(matches!(
+            chunker.nth_chunk(0, 0, None),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+
+    #[test]
+    fn new_populated() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new_populated(&file).unwrap();
+        assert_eq!(chunker.as_bytes(), HAPROXY_LOG.as_bytes());
         assert_eq!(
-            String::from_utf8_lossy(chunks[1]),
-            log[(1 * (chunk_size + 1))..=(2 * chunk_size + 1)].to_string()
+            chunker.chunks(4, Some('\n')).unwrap(),
+            FileChunker::new(&file)
+                .unwrap()
+                .chunks(4, Some('\n'))
+                .unwrap()
         );
+    }
+
+    #[test]
+    fn chunks_balanced() {
+        let log = "00000\n111111111111\n2\n3\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
         assert_eq!(
-            String::from_utf8_lossy(chunks[2]),
-            log[(2 * (chunk_size + 1))..=(3 * chunk_size + 2)].to_string()
+            chunker.chunks(2, Some('\n')).unwrap(),
+            vec![&b"00000\n111111111111\n"[..], b"2\n3\n"]
         );
         assert_eq!(
-            String::from_utf8_lossy(chunks[3]),
-            log[(3 * (chunk_size + 1))..=(4 * chunk_size + 3)].to_string()
+            chunker.chunks_balanced(2, '\n').unwrap(),
+            vec![&b"00000\n"[..], b"111111111111\n2\n3\n"]
         );
+        assert_eq!(chunker.chunks_balanced(100, '\n').unwrap().len(), 4);
         assert_eq!(
-            String::from_utf8_lossy(chunks[4]),
-            log[(4 * (chunk_size + 1))..].to_string()
+            chunker.chunks_balanced(1, '\n').unwrap(),
+            vec![log.as_bytes()]
         );
+        assert!(matches!(
+            chunker.chunks_balanced(0, '\n'),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
     }
 
     #[test]
-    fn chunks_with_delimiter_single_chunk() {
-        let log = "Why, sometimes I've believed as many as six impossible things before breakfast.";
-
+    fn chunks_balanced_tiles() {
         let mut file: File = tempfile::tempfile().unwrap();
-        file.write_all(log.as_bytes()).unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
         file.flush().unwrap();
 
-        let chunk_count = 1;
         let chunker = FileChunker::new(&file).unwrap();
-        let chunks = chunker.chunks(chunk_count, Some('\n')).unwrap();
+        for count in 1..=12 {
+            let chunks = chunker.chunks_balanced(count, '\n').unwrap();
+            assert!(chunker.verify_tiling(&chunks));
+            assert!(chunks.len() <= count);
+        }
+    }
 
-        assert_eq!(chunks[0].len(), log.len());
-        assert_eq!(chunks.len(), 1);
+    #[cfg(unix)]
+    #[test]
+    fn from_raw_fd() {
+        use std::os::unix::io::AsRawFd;
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(HAPROXY_LOG.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = unsafe { FileChunker::from_raw_fd(file.as_raw_fd()).unwrap() };
+        assert_eq!(chunker.as_bytes(), HAPROXY_LOG.as_bytes());
+
+        // The descriptor is still open and usable after the chunker borrowed it.
+        file.write_all(b"more").unwrap();
+        drop(file);
+        assert_eq!(chunker.len(), HAPROXY_LOG.len());
     }
 }
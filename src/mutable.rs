@@ -0,0 +1,135 @@
+use crate::{check_addressable, chunk_size, ChunkIter, ChunkerError, FileChunker, Result};
+use memmap2::MmapMut;
+use std::fmt;
+use std::fs::File;
+
+/// Divides a writable memory mapping of a file into mutable chunks, for transforming the file in
+/// place. Created with [`FileChunker::new_mut`].
+pub struct FileChunkerMut {
+    mmap: MmapMut,
+}
+
+impl fmt::Debug for FileChunkerMut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileChunkerMut")
+            .field("len", &self.mmap.len())
+            .finish()
+    }
+}
+
+impl FileChunker {
+    /// Create a new FileChunkerMut, which maps the file for writing. The file must have been
+    /// opened with write access. Changes made through the chunks are written back to the file,
+    /// either eventually by the OS or immediately with [`FileChunkerMut::flush`].
+    ///
+    /// Returns [`ChunkerError::FileTooLarge`] if the file does not fit in the address space.
+    pub fn new_mut(file: &File) -> Result<FileChunkerMut> {
+        check_addressable(file)?;
+        let mmap = unsafe { MmapMut::map_mut(file)? };
+        Ok(FileChunkerMut { mmap })
+    }
+}
+
+impl FileChunkerMut {
+    /// Returns the contents of the mapping.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    /// Divide the file into mutable chunks, placing boundaries exactly as
+    /// [`FileChunker::chunks`] does. The chunks don't overlap, so they can be modified
+    /// independently, including from different threads.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkCount`] if `count` is zero.
+    pub fn chunks_mut(&mut self, count: usize, delimiter: Option<char>) -> Result<Vec<&mut [u8]>> {
+        if count == 0 {
+            return Err(ChunkerError::InvalidChunkCount);
+        }
+        let chunk_size = chunk_size(self.mmap.len(), count).max(1);
+        let ends: Vec<usize> = ChunkIter::new(&self.mmap, chunk_size, delimiter.map(|d| d as u8))
+            .into_ranges()
+            .map(|range| range.end)
+            .collect();
+
+        // Splitting off each chunk in turn hands out disjoint borrows of the mapping.
+        let mut rest: &mut [u8] = &mut self.mmap;
+        let mut offset = 0;
+        let mut chunks = Vec::with_capacity(ends.len());
+        for end in ends {
+            let (chunk, tail) = std::mem::take(&mut rest).split_at_mut(end - offset);
+            chunks.push(chunk);
+            rest = tail;
+            offset = end;
+        }
+
+        Ok(chunks)
+    }
+
+    /// Synchronously write any changes back to the file.
+    pub fn flush(&self) -> Result<()> {
+        self.mmap.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ChunkerError, FileChunker};
+    use std::fs::File;
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    #[test]
+    fn chunks_mut_matches_chunks() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let mut chunker_mut = FileChunker::new_mut(&file).unwrap();
+        for count in 1..=log.len() {
+            for delimiter in [None, Some('\n')] {
+                let expected: Vec<Vec<u8>> = chunker
+                    .chunks(count, delimiter)
+                    .unwrap()
+                    .into_iter()
+                    .map(<[u8]>::to_vec)
+                    .collect();
+                assert_eq!(chunker_mut.chunks_mut(count, delimiter).unwrap(), expected);
+            }
+        }
+        assert!(matches!(
+            chunker_mut.chunks_mut(0, None),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+
+    #[test]
+    fn chunks_mut_writes_through() {
+        let log = "user=alice\nuser=bob\nuser=carol\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let mut chunker = FileChunker::new_mut(&file).unwrap();
+        std::thread::scope(|scope| {
+            for chunk in chunker.chunks_mut(3, Some('\n')).unwrap() {
+                scope.spawn(move || {
+                    for line in chunk.split_mut(|&b| b == b'\n') {
+                        if let Some(eq) = line.iter().position(|&b| b == b'=') {
+                            line[eq + 1..].fill(b'*');
+                        }
+                    }
+                });
+            }
+        });
+        chunker.flush().unwrap();
+
+        let mut contents = String::new();
+        file.seek(SeekFrom::Start(0)).unwrap();
+        file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "user=*****\nuser=***\nuser=*****\n");
+    }
+}
@@ -0,0 +1,852 @@
+use crate::{chunk_size, Boundary, ChunkIter, ChunkerError, FileChunker, Result};
+
+/// How the bytes of a file are spread between chunks when its length isn't a multiple of the
+/// chunk count.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChunkDistribution {
+    /// Every chunk's nominal size is the file length divided by the count, rounded up, so the
+    /// last chunk is smaller than the rest by up to `count - 1` bytes.
+    #[default]
+    Ceiling,
+    /// The remainder of the division is spread one byte at a time across the first chunks, so
+    /// nominal chunk sizes differ by at most one byte.
+    Even,
+}
+
+// The number of chunks that `OnInsufficientRecords::Pad` can always pad up to, however short the
+// file is. This is enough for one chunk per thread on any machine.
+const MIN_PAD_LIMIT: usize = 4096;
+
+/// What to do when a file has too few records to fill the requested number of chunks, so
+/// delimiter alignment produces fewer chunks than [`ChunkerOptions::count`] asks for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnInsufficientRecords {
+    /// Return the chunks that could be produced, even if there are fewer than requested.
+    #[default]
+    Proceed,
+    /// Return [`ChunkerError::InsufficientChunks`].
+    Error,
+    /// Append empty chunks at the end of the file until there are as many as requested.
+    ///
+    /// Each padding chunk takes up memory, so a count that is far larger than the file could
+    /// ever fill, more than the file length in bytes or 4096, whichever is larger, returns
+    /// [`ChunkerError::InsufficientChunks`] instead.
+    Pad,
+}
+
+/// A builder for configuring how a file is divided into chunks.
+///
+/// This is created by [`FileChunker::options`]. Options that aren't set keep their defaults: a
+/// single chunk, no delimiter, and delimiters kept at the end of each chunk.
+///
+/// ```rust,no_run
+/// use file_chunker::FileChunker;
+///
+/// let chunker = FileChunker::open("/path/to/file").unwrap();
+/// let chunks = chunker
+///     .options()
+///     .count(8)
+///     .delimiter(b'\n')
+///     .trim_delimiter(true)
+///     .build()
+///     .unwrap();
+/// ```
+pub struct ChunkerOptions<'a> {
+    chunker: &'a FileChunker,
+    count: usize,
+    max_bytes: Option<usize>,
+    min_chunk_size: usize,
+    max_chunks: Option<usize>,
+    preferred_alignment: usize,
+    distribution: ChunkDistribution,
+    on_insufficient_records: OnInsufficientRecords,
+    delimiter: Option<u8>,
+    boundary: Boundary,
+    trim_delimiter: bool,
+    allow_empty_chunk: bool,
+    skip_empty: bool,
+    drop_leading_partial: bool,
+    complete_trailing_partial: bool,
+    collapse_runs: bool,
+    page_aligned: bool,
+    max_total_bytes: Option<usize>,
+}
+
+impl<'a> ChunkerOptions<'a> {
+    pub(crate) fn new(chunker: &'a FileChunker) -> Self {
+        Self {
+            chunker,
+            count: 1,
+            max_bytes: None,
+            min_chunk_size: 0,
+            max_chunks: None,
+            preferred_alignment: 1,
+            distribution: ChunkDistribution::Ceiling,
+            on_insufficient_records: OnInsufficientRecords::Proceed,
+            delimiter: None,
+            boundary: Boundary::TrailingDelimiter,
+            trim_delimiter: false,
+            allow_empty_chunk: false,
+            skip_empty: false,
+            drop_leading_partial: false,
+            complete_trailing_partial: false,
+            collapse_runs: false,
+            page_aligned: false,
+            max_total_bytes: None,
+        }
+    }
+
+    /// Divide the file into approximately `count` chunks of equal size.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Divide the file into chunks of at most `max_bytes` bytes before delimiter alignment. This
+    /// takes precedence over [`count`](Self::count).
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Only chunk the start of the file, up to `max_total_bytes` bytes, such as to quickly
+    /// sample a huge file. With a delimiter, the limit is extended to the end of the record it
+    /// falls in, or the end of the file if there is no later delimiter. The chunks then divide
+    /// just that prefix, so [`count`](Self::count) and the other options apply to its length
+    /// rather than the whole file's.
+    pub fn max_total_bytes(mut self, max_total_bytes: usize) -> Self {
+        self.max_total_bytes = Some(max_total_bytes);
+        self
+    }
+
+    /// Never make chunks smaller than `min_chunk_size` bytes, even if that means producing fewer
+    /// chunks than [`count`](Self::count) asks for. This avoids splitting a small file into many
+    /// tiny chunks.
+    ///
+    /// The floor applies before delimiter alignment. Since alignment only ever moves a boundary
+    /// forward, every chunk except the last is still at least `min_chunk_size` bytes long; the
+    /// last chunk holds whatever remains.
+    pub fn min_chunk_size(mut self, min_chunk_size: usize) -> Self {
+        self.min_chunk_size = min_chunk_size;
+        self
+    }
+
+    /// Never produce more than `max_chunks` chunks. If [`max_bytes`](Self::max_bytes) or
+    /// [`count`](Self::count) would produce more, the chunk size is increased until they fit, so
+    /// this cap wins over `max_bytes` when the two conflict. This guards against a tiny
+    /// `max_bytes` producing a huge number of chunks for a large file.
+    pub fn max_chunks(mut self, max_chunks: usize) -> Self {
+        self.max_chunks = Some(max_chunks);
+        self
+    }
+
+    /// Move each nominal chunk boundary forward to the next multiple of `alignment` bytes before
+    /// looking for a delimiter, such as to match a compressor's block size. Without a delimiter,
+    /// every boundary except the end of the file falls on a multiple of `alignment`.
+    ///
+    /// Delimiter alignment always takes precedence: with a delimiter, the boundary is placed at
+    /// the first delimiter at or past the aligned position, which is generally not itself a
+    /// multiple of `alignment`. An alignment of zero or one has no effect.
+    pub fn preferred_alignment(mut self, alignment: usize) -> Self {
+        self.preferred_alignment = alignment;
+        self
+    }
+
+    /// Align nominal chunk boundaries to [`FileChunker::page_size`], like
+    /// [`preferred_alignment`](Self::preferred_alignment) with the page size, so that workers
+    /// processing different chunks of a memory-mapped file mostly touch different pages. With a
+    /// delimiter, each boundary is placed at the first delimiter at or past a page boundary.
+    ///
+    /// This takes precedence over `preferred_alignment`.
+    pub fn page_aligned(mut self, page_aligned: bool) -> Self {
+        self.page_aligned = page_aligned;
+        self
+    }
+
+    /// Choose how the file length is divided between the [`count`](Self::count) chunks. This
+    /// has no effect with [`max_bytes`](Self::max_bytes), or when
+    /// [`min_chunk_size`](Self::min_chunk_size) or [`max_chunks`](Self::max_chunks) changes the
+    /// chunk size.
+    pub fn distribution(mut self, distribution: ChunkDistribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+
+    /// Cut chunks at their nominal sizes instead of at delimiters, and drop the partial record
+    /// at the start of every chunk but the first, up to and including the first delimiter. A
+    /// record that starts exactly at the start of a chunk is kept. This suits workers that
+    /// each receive a byte range and must decide independently which records they own.
+    ///
+    /// The dropped partial record belongs to the previous chunk, which only includes it if
+    /// [`complete_trailing_partial`](Self::complete_trailing_partial) is also set. Together the
+    /// two options assign every record to exactly one chunk. A chunk that lies entirely within
+    /// one record becomes empty. This has no effect without a delimiter.
+    pub fn drop_leading_partial(mut self, drop_leading_partial: bool) -> Self {
+        self.drop_leading_partial = drop_leading_partial;
+        self
+    }
+
+    /// With [`drop_leading_partial`](Self::drop_leading_partial), extend each chunk past its
+    /// nominal end to complete the record that straddles it. This has no effect otherwise.
+    pub fn complete_trailing_partial(mut self, complete_trailing_partial: bool) -> Self {
+        self.complete_trailing_partial = complete_trailing_partial;
+        self
+    }
+
+    /// Choose what happens when fewer chunks than [`count`](Self::count), capped by
+    /// [`max_chunks`](Self::max_chunks), can be produced. This is checked after every other
+    /// option has been applied, so chunks lost to [`min_chunk_size`](Self::min_chunk_size) or
+    /// [`skip_empty`](Self::skip_empty) count as missing too. It has no effect with
+    /// [`max_bytes`](Self::max_bytes).
+    pub fn on_insufficient_records(mut self, behavior: OnInsufficientRecords) -> Self {
+        self.on_insufficient_records = behavior;
+        self
+    }
+
+    /// Align chunk boundaries to the given delimiter byte.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Choose which side of the delimiter each chunk boundary falls on.
+    pub fn boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Exclude the boundary delimiter from each chunk. With
+    /// [`Boundary::TrailingDelimiter`], the delimiter at the end of each chunk is removed; with
+    /// [`Boundary::LeadingDelimiter`], the delimiter at the start of each chunk is removed.
+    pub fn trim_delimiter(mut self, trim_delimiter: bool) -> Self {
+        self.trim_delimiter = trim_delimiter;
+        self
+    }
+
+    /// Return a single empty chunk for an empty file, instead of no chunks at all. This is
+    /// useful for callers that expect at least one chunk.
+    pub fn allow_empty_chunk(mut self, allow_empty_chunk: bool) -> Self {
+        self.allow_empty_chunk = allow_empty_chunk;
+        self
+    }
+
+    /// Treat a run of consecutive delimiters as a single boundary, as with paragraphs separated
+    /// by blank lines. Each chunk keeps one delimiter at its end, and the rest of the run is left
+    /// out, along with any delimiters at the start of the file. Chunks that would hold only
+    /// delimiters are left out entirely.
+    ///
+    /// Since bytes are left out, the chunks no longer add up to the whole file. Runs inside a
+    /// chunk are kept as they are. This has no effect without a delimiter, and assumes
+    /// [`Boundary::TrailingDelimiter`].
+    pub fn collapse_runs(mut self, collapse_runs: bool) -> Self {
+        self.collapse_runs = collapse_runs;
+        self
+    }
+
+    /// Leave out chunks that are empty. Without trimming, every chunk holds at least one byte,
+    /// but with [`trim_delimiter`](Self::trim_delimiter) a chunk holding only a delimiter, as
+    /// happens with back-to-back delimiters, becomes empty. This also drops the chunk produced
+    /// by [`allow_empty_chunk`](Self::allow_empty_chunk).
+    pub fn skip_empty(mut self, skip_empty: bool) -> Self {
+        self.skip_empty = skip_empty;
+        self
+    }
+
+    /// Divide the file into chunks using the configured options.
+    ///
+    /// Returns [`ChunkerError::InvalidChunkCount`] if the count or maximum chunk count is zero, or
+    /// [`ChunkerError::InvalidChunkSize`] if the maximum chunk size is zero.
+    pub fn build(self) -> Result<Vec<&'a [u8]>> {
+        let mut data = self.chunker.as_bytes();
+        if let Some(limit) = self.max_total_bytes.filter(|&limit| limit < data.len()) {
+            let end = match (self.delimiter, self.boundary) {
+                (None, _) => limit,
+                (Some(delimiter), Boundary::TrailingDelimiter) => {
+                    next_record_start(data, limit, delimiter)
+                }
+                (Some(delimiter), Boundary::LeadingDelimiter) => {
+                    memchr::memchr(delimiter, &data[limit..]).map_or(data.len(), |i| limit + i)
+                }
+            };
+            data = &data[..end];
+        }
+        let capped_size = match self.max_chunks {
+            Some(0) => return Err(ChunkerError::InvalidChunkCount),
+            Some(max_chunks) => chunk_size(data.len(), max_chunks),
+            None => 0,
+        };
+        let nominal_size = chunk_size(data.len(), self.count).max(1);
+        let chunk_size = match self.max_bytes {
+            Some(0) => return Err(ChunkerError::InvalidChunkSize),
+            Some(max_bytes) => max_bytes,
+            None if self.count == 0 => return Err(ChunkerError::InvalidChunkCount),
+            None => nominal_size,
+        }
+        .max(self.min_chunk_size)
+        .max(capped_size);
+        let remainder = data.len() % self.count.max(1);
+        let (chunk_size, extra_bytes) = match self.distribution {
+            ChunkDistribution::Even
+                if self.max_bytes.is_none()
+                    && remainder > 0
+                    && chunk_size > 1
+                    && chunk_size == nominal_size =>
+            {
+                (chunk_size - 1, remainder)
+            }
+            _ => (chunk_size, 0),
+        };
+
+        let alignment = match self.page_aligned {
+            true => FileChunker::page_size(),
+            false => self.preferred_alignment,
+        };
+        let mut chunks: Vec<&'a [u8]> = if data.is_empty() && self.allow_empty_chunk {
+            vec![data]
+        } else if let (true, Some(delimiter)) = (self.drop_leading_partial, self.delimiter) {
+            ChunkIter::new(data, chunk_size, None)
+                .with_alignment(alignment)
+                .with_extra_bytes(extra_bytes)
+                .into_ranges()
+                .map(|range| {
+                    let start = next_record_start(data, range.start, delimiter);
+                    let end = match self.complete_trailing_partial {
+                        true => next_record_start(data, range.end, delimiter),
+                        false => range.end,
+                    };
+                    &data[start..end.max(start)]
+                })
+                .collect()
+        } else {
+            ChunkIter::new(data, chunk_size, self.delimiter)
+                .with_boundary(self.boundary)
+                .with_alignment(alignment)
+                .with_extra_bytes(extra_bytes)
+                .collect()
+        };
+        if let (true, Some(delimiter)) = (self.collapse_runs, self.delimiter) {
+            for chunk in &mut chunks {
+                let start = chunk.iter().position(|&b| b != delimiter);
+                let end = chunk.iter().rposition(|&b| b != delimiter);
+                *chunk = match (start, end) {
+                    (Some(start), Some(end)) => &chunk[start..(end + 2).min(chunk.len())],
+                    _ => &chunk[chunk.len()..],
+                };
+            }
+            chunks.retain(|chunk| !chunk.is_empty());
+        }
+        if let (true, Some(delimiter)) = (self.trim_delimiter, self.delimiter) {
+            let delimiter = [delimiter];
+            for chunk in &mut chunks {
+                *chunk = match self.boundary {
+                    Boundary::TrailingDelimiter => chunk.strip_suffix(&delimiter).unwrap_or(chunk),
+                    Boundary::LeadingDelimiter => chunk.strip_prefix(&delimiter).unwrap_or(chunk),
+                };
+            }
+        }
+        if self.skip_empty {
+            chunks.retain(|chunk| !chunk.is_empty());
+        }
+
+        let requested = self.count.min(self.max_chunks.unwrap_or(usize::MAX));
+        if self.max_bytes.is_none() && chunks.len() < requested {
+            match self.on_insufficient_records {
+                OnInsufficientRecords::Proceed => {}
+                OnInsufficientRecords::Pad if requested <= data.len().max(MIN_PAD_LIMIT) => {
+                    chunks.resize(requested, &data[data.len()..])
+                }
+                OnInsufficientRecords::Error | OnInsufficientRecords::Pad => {
+                    return Err(ChunkerError::InsufficientChunks {
+                        requested,
+                        achieved: chunks.len(),
+                    })
+                }
+            }
+        }
+
+        Ok(chunks)
+    }
+}
+
+// Returns the start of the first record that begins at or after `position`.
+fn next_record_start(data: &[u8], position: usize, delimiter: u8) -> usize {
+    if position == 0 {
+        return 0;
+    }
+    memchr::memchr(delimiter, &data[position - 1..]).map_or(data.len(), |i| position + i)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Boundary, ChunkDistribution, ChunkerError, FileChunker, OnInsufficientRecords};
+    use std::fs::File;
+    use std::io::Write;
+
+    #[test]
+    fn options_match_chunks() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.options().count(2).delimiter(b'\n').build().unwrap();
+        assert_eq!(chunks, chunker.chunks(2, Some('\n')).unwrap());
+
+        let chunks = chunker.options().max_bytes(4).build().unwrap();
+        assert_eq!(chunks, chunker.chunks_by_size(4, None).unwrap());
+
+        let chunks = chunker.options().build().unwrap();
+        assert_eq!(chunks, vec![log.as_bytes()]);
+    }
+
+    #[test]
+    fn options_trim_delimiter() {
+        let log = "\x1e01\x1e23\x1e45\x1e67\x1e89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .options()
+            .count(2)
+            .delimiter(b'\x1e')
+            .trim_delimiter(true)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, chunker.chunks_trim_delimiter(2, '\x1e').unwrap());
+
+        let chunks = chunker
+            .options()
+            .count(2)
+            .delimiter(b'\x1e')
+            .boundary(Boundary::LeadingDelimiter)
+            .trim_delimiter(true)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, vec![&b"01\x1e23\x1e45"[..], b"67\x1e89"]);
+    }
+
+    #[test]
+    fn options_min_chunk_size() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .options()
+            .count(14)
+            .delimiter(b'\n')
+            .min_chunk_size(5)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, vec![&b"01\n23\n"[..], b"45\n67\n", b"89"]);
+
+        let chunks = chunker
+            .options()
+            .count(2)
+            .min_chunk_size(5)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, chunker.chunks(2, None).unwrap());
+    }
+
+    #[test]
+    fn options_skip_empty() {
+        let log = "01\n\n\n23";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let options = || {
+            chunker
+                .options()
+                .count(log.len())
+                .delimiter(b'\n')
+                .boundary(Boundary::LeadingDelimiter)
+                .trim_delimiter(true)
+        };
+        let chunks = options().build().unwrap();
+        assert_eq!(chunks, vec![&b"01"[..], b"", b"", b"23"]);
+        let chunks = options().skip_empty(true).build().unwrap();
+        assert_eq!(chunks, vec![&b"01"[..], b"23"]);
+
+        let empty: File = tempfile::tempfile().unwrap();
+        let chunker = FileChunker::new(&empty).unwrap();
+        let chunks = chunker
+            .options()
+            .allow_empty_chunk(true)
+            .skip_empty(true)
+            .build()
+            .unwrap();
+        assert!(chunks.is_empty());
+    }
+
+    #[test]
+    fn options_max_chunks() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .options()
+            .max_bytes(1)
+            .max_chunks(3)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, vec![&b"01\n23"[..], b"\n45\n6", b"7\n89"]);
+
+        let chunks = chunker
+            .options()
+            .max_bytes(6)
+            .max_chunks(3)
+            .delimiter(b'\n')
+            .build()
+            .unwrap();
+        assert_eq!(chunks, chunker.chunks_by_size(6, Some('\n')).unwrap());
+
+        let chunks = chunker.options().count(7).max_chunks(2).build().unwrap();
+        assert_eq!(chunks, chunker.chunks(2, None).unwrap());
+
+        assert!(matches!(
+            chunker.options().max_chunks(0).build(),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+
+    #[test]
+    fn options_preferred_alignment() {
+        let log = "0123456789abcdefghij";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .options()
+            .max_bytes(3)
+            .preferred_alignment(4)
+            .build()
+            .unwrap();
+        assert_eq!(
+            chunks,
+            vec![&b"0123"[..], b"4567", b"89ab", b"cdef", b"ghij"]
+        );
+
+        let log = "012\n45\n789\nbcd\nfgh\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .options()
+            .max_bytes(5)
+            .preferred_alignment(8)
+            .delimiter(b'\n')
+            .build()
+            .unwrap();
+        assert_eq!(chunks, vec![&b"012\n45\n789\n"[..], b"bcd\nfgh\n"]);
+
+        for alignment in [0, 1] {
+            let chunks = chunker
+                .options()
+                .count(3)
+                .preferred_alignment(alignment)
+                .delimiter(b'\n')
+                .build()
+                .unwrap();
+            assert_eq!(chunks, chunker.chunks(3, Some('\n')).unwrap());
+        }
+    }
+
+    #[test]
+    fn options_even_distribution() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker.options().count(4).build().unwrap();
+        assert_eq!(chunks, vec![&b"012"[..], b"345", b"678", b"9"]);
+
+        let chunks = chunker
+            .options()
+            .count(4)
+            .distribution(ChunkDistribution::Even)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, vec![&b"012"[..], b"345", b"67", b"89"]);
+
+        for count in 1..=12 {
+            let chunks = chunker
+                .options()
+                .count(count)
+                .distribution(ChunkDistribution::Even)
+                .build()
+                .unwrap();
+            assert_eq!(chunks.len(), count.min(log.len()));
+            let min = chunks.iter().map(|chunk| chunk.len()).min().unwrap();
+            let max = chunks.iter().map(|chunk| chunk.len()).max().unwrap();
+            assert!(max - min <= 1, "count {}", count);
+        }
+    }
+
+    #[test]
+    fn options_on_insufficient_records() {
+        for (log, achieved) in [("01\n23\n", 2), ("\n\n\n", 2)] {
+            let mut file: File = tempfile::tempfile().unwrap();
+            file.write_all(log.as_bytes()).unwrap();
+            file.flush().unwrap();
+
+            let chunker = FileChunker::new(&file).unwrap();
+            let options = |behavior| {
+                chunker
+                    .options()
+                    .count(4)
+                    .delimiter(b'\n')
+                    .on_insufficient_records(behavior)
+            };
+
+            let chunks = options(OnInsufficientRecords::Proceed).build().unwrap();
+            assert_eq!(chunks, chunker.chunks(4, Some('\n')).unwrap());
+            assert_eq!(chunks.len(), achieved);
+
+            assert!(matches!(
+                options(OnInsufficientRecords::Error).build(),
+                Err(ChunkerError::InsufficientChunks { requested: 4, achieved: a }) if a == achieved
+            ));
+
+            let padded = options(OnInsufficientRecords::Pad).build().unwrap();
+            assert_eq!(padded.len(), 4);
+            assert_eq!(&padded[..achieved], &chunks[..]);
+            assert!(padded[achieved..].iter().all(|chunk| chunk.is_empty()));
+
+            assert_eq!(
+                options(OnInsufficientRecords::Pad)
+                    .count(4096)
+                    .build()
+                    .unwrap()
+                    .len(),
+                4096
+            );
+            assert!(matches!(
+                options(OnInsufficientRecords::Pad).count(usize::MAX / 2).build(),
+                Err(ChunkerError::InsufficientChunks { requested, achieved: a })
+                    if requested == usize::MAX / 2 && a == achieved
+            ));
+        }
+    }
+
+    #[test]
+    fn options_drop_leading_partial() {
+        let log = "01\n2345\n6\n789\nabcdefgh\ni";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .options()
+            .max_bytes(4)
+            .delimiter(b'\n')
+            .drop_leading_partial(true)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, vec![&b"01\n2"[..], b"", b"6\n78", b"ab", b"", b"i"]);
+
+        for max_bytes in 1..=log.len() {
+            let chunks = chunker
+                .options()
+                .max_bytes(max_bytes)
+                .delimiter(b'\n')
+                .drop_leading_partial(true)
+                .complete_trailing_partial(true)
+                .build()
+                .unwrap();
+            assert_eq!(chunks.concat(), log.as_bytes(), "max_bytes {}", max_bytes);
+            let records: Vec<&[u8]> = chunks
+                .iter()
+                .flat_map(|chunk| chunk.split_inclusive(|&b| b == b'\n'))
+                .collect();
+            assert_eq!(
+                records,
+                log.as_bytes()
+                    .split_inclusive(|&b| b == b'\n')
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn options_collapse_runs() {
+        let log = "\n\nab\n\n\ncd\nef\n\n\n\ngh";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .options()
+            .count(log.len())
+            .delimiter(b'\n')
+            .collapse_runs(true)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, vec![&b"ab\n"[..], b"cd\n", b"ef\n", b"gh"]);
+
+        let chunks = chunker
+            .options()
+            .count(2)
+            .delimiter(b'\n')
+            .collapse_runs(true)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, vec![&b"ab\n\n\ncd\n"[..], b"ef\n\n\n\ngh"]);
+    }
+
+    #[test]
+    fn options_page_aligned() {
+        let page_size = FileChunker::page_size();
+        let record = "0123456789abcde\n";
+        let log = record.repeat(page_size * 4 / record.len() + 3);
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .options()
+            .count(8)
+            .page_aligned(true)
+            .build()
+            .unwrap();
+        assert_eq!(chunks.len(), 5);
+        assert!(chunks[..4].iter().all(|chunk| chunk.len() == page_size));
+
+        let chunks = chunker
+            .options()
+            .count(8)
+            .delimiter(b'\n')
+            .page_aligned(true)
+            .build()
+            .unwrap();
+        assert_eq!(chunks.concat(), log.as_bytes());
+        let mut end = 0;
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!(chunk.ends_with(b"\n"));
+            end += chunk.len();
+            let page_boundary = (end - 1) / page_size * page_size;
+            assert!(log.as_bytes()[page_boundary..end - 1]
+                .iter()
+                .all(|&b| b != b'\n'));
+        }
+    }
+
+    #[test]
+    fn options_max_total_bytes() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let limited = |limit, delimiter: Option<u8>| {
+            let options = chunker.options().count(2).max_total_bytes(limit);
+            match delimiter {
+                Some(delimiter) => options.delimiter(delimiter),
+                None => options,
+            }
+            .build()
+            .unwrap()
+        };
+        assert_eq!(limited(7, Some(b'\n')), vec![&b"01\n23\n"[..], b"45\n"]);
+        assert_eq!(limited(6, Some(b'\n')), vec![&b"01\n23\n"[..]]);
+        assert_eq!(limited(7, None), vec![&b"01\n2"[..], b"3\n4"]);
+        assert_eq!(limited(13, Some(b'\n')).concat(), log.as_bytes());
+        assert_eq!(limited(100, None).concat(), log.as_bytes());
+        assert!(limited(0, Some(b'\n')).is_empty());
+
+        let chunks = chunker
+            .options()
+            .delimiter(b'\n')
+            .boundary(Boundary::LeadingDelimiter)
+            .max_total_bytes(4)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, vec![&b"01\n23"[..]]);
+    }
+
+    #[test]
+    fn options_invalid() {
+        let log = "0123456789";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        assert!(matches!(
+            chunker.options().count(0).build(),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+        assert!(matches!(
+            chunker.options().max_bytes(0).build(),
+            Err(ChunkerError::InvalidChunkSize)
+        ));
+    }
+
+    #[test]
+    fn options_empty_file() {
+        let file: File = tempfile::tempfile().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for delimiter in [None, Some(b'\n')] {
+            let mut options = chunker.options().count(2);
+            if let Some(delimiter) = delimiter {
+                options = options.delimiter(delimiter);
+            }
+            let chunks = options.allow_empty_chunk(true).build().unwrap();
+            assert_eq!(chunks, vec![&b""[..]]);
+        }
+        assert!(chunker.options().count(2).build().unwrap().is_empty());
+    }
+
+    #[test]
+    fn options_allow_empty_chunk_non_empty_file() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let chunks = chunker
+            .options()
+            .count(2)
+            .delimiter(b'\n')
+            .allow_empty_chunk(true)
+            .build()
+            .unwrap();
+        assert_eq!(chunks, chunker.chunks(2, Some('\n')).unwrap());
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{FileChunker, Result};
+use rayon::prelude::*;
+
+impl FileChunker {
+    /// Divide the file into chunks like [`chunks`](Self::chunks), and call `f` on each chunk in
+    /// parallel using the global rayon thread pool. Blocks until every chunk has been processed.
+    ///
+    /// Requires the `rayon` feature.
+    pub fn par_for_each<F>(&self, count: usize, delimiter: Option<char>, f: F) -> Result<()>
+    where
+        F: Fn(&[u8]) + Sync + Send,
+    {
+        self.chunks(count, delimiter)?.into_par_iter().for_each(f);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::FileChunker;
+    use std::fs::File;
+    use std::io::Write;
+    use std::sync::Mutex;
+
+    #[test]
+    fn par_for_each_visits_every_chunk() {
+        let log = "01\n23\n45\n67\n89\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let seen = Mutex::new(Vec::new());
+        chunker
+            .par_for_each(5, Some('\n'), |chunk| {
+                seen.lock().unwrap().push(chunk.to_vec());
+            })
+            .unwrap();
+
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(seen.concat(), log.as_bytes());
+        assert_eq!(seen.len(), chunker.chunks(5, Some('\n')).unwrap().len());
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{FileChunker, Result};
+use regex::bytes::Regex;
+
+impl FileChunker {
+    /// Divide the file into chunks of approximately equal size, where each chunk ends with a
+    /// match of `boundary`. This frames records that a single delimiter can't, such as
+    /// pretty-printed JSON objects, which contain newlines but end with a `}` at the start of a
+    /// line: `(?m)^\}\n`.
+    ///
+    /// Like [`chunks`](Self::chunks), the search for each boundary starts at the nominal chunk
+    /// size, and the chunk ends after the first match that starts there or later. The match may
+    /// look at bytes before that point, so anchors such as `^` behave as expected.
+    ///
+    /// Matching a regex is considerably slower than the byte search used for single-byte
+    /// delimiters, although only the bytes between the nominal boundary and the match are
+    /// scanned. Patterns that begin with a literal, like the one above, are the fastest, since
+    /// the regex engine can skip ahead to occurrences of it.
+    ///
+    /// Requires the `regex` feature.
+    pub fn chunks_regex(&self, count: usize, boundary: &Regex) -> Result<Vec<&[u8]>> {
+        let chunk_size = self.chunk_size(count)?;
+        let data = self.as_bytes();
+        let mut chunks = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() {
+            let candidate = offset.saturating_add(chunk_size).min(data.len());
+            let chunk_end = boundary
+                .find_at(data, candidate)
+                .map_or(data.len(), |m| m.end());
+            chunks.push(&data[offset..chunk_end]);
+            offset = chunk_end;
+        }
+
+        Ok(chunks)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ChunkerError, FileChunker};
+    use regex::bytes::Regex;
+    use std::fs::File;
+    use std::io::Write;
+
+    #[test]
+    fn chunks_regex() {
+        let log = "{\n  \"a\": {\n    \"b\": 1\n  }\n}\n{\n  \"c\": 2\n}\n{\n  \"d\": 3\n}\n";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        let boundary = Regex::new(r"(?m)^\}\n").unwrap();
+        assert_eq!(
+            chunker.chunks_regex(log.len(), &boundary).unwrap(),
+            vec![
+                &b"{\n  \"a\": {\n    \"b\": 1\n  }\n}\n"[..],
+                b"{\n  \"c\": 2\n}\n",
+                b"{\n  \"d\": 3\n}\n",
+            ]
+        );
+        assert_eq!(
+            chunker.chunks_regex(1, &boundary).unwrap(),
+            vec![log.as_bytes()]
+        );
+        assert!(matches!(
+            chunker.chunks_regex(0, &boundary),
+            Err(ChunkerError::InvalidChunkCount)
+        ));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{split, ChunkIter, Chunker, Result};
+
+/// Divides an in-memory byte slice into chunks, using the same algorithm as
+/// [`FileChunker`](crate::FileChunker). This is useful when the data is already in memory, such
+/// as after downloading or decompressing it, and there is no file to map.
+#[derive(Clone, Copy, Debug)]
+pub struct SliceChunker<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> SliceChunker<'a> {
+    /// Create a new SliceChunker over `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Returns the slice that the chunker divides into chunks.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Divide the slice into chunks of approximately equal size. See
+    /// [`FileChunker::chunks`](crate::FileChunker::chunks).
+    pub fn chunks(&self, count: usize, delimiter: Option<char>) -> Result<Vec<&'a [u8]>> {
+        split(self.data, count, delimiter.map(|d| d as u8))
+    }
+
+    /// Lazily divide the slice into chunks of approximately equal size. See
+    /// [`FileChunker::chunk_iter`](crate::FileChunker::chunk_iter).
+    pub fn chunk_iter(&self, count: usize, delimiter: Option<char>) -> ChunkIter<'a> {
+        let chunk_size = crate::chunk_size(self.data.len(), count).max(1);
+        ChunkIter::new(self.data, chunk_size, delimiter.map(|d| d as u8))
+    }
+}
+
+impl Chunker for SliceChunker<'_> {
+    fn as_bytes(&self) -> &[u8] {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Chunker, FileChunker, SliceChunker};
+    use std::fs::File;
+    use std::io::Write;
+
+    #[test]
+    fn slice_chunker_matches_file_chunker() {
+        let log = "01\n23\n45\n67\n89";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let file_chunker = FileChunker::new(&file).unwrap();
+        let slice_chunker = SliceChunker::new(log.as_bytes());
+        for count in 1..=log.len() {
+            for delimiter in [None, Some('\n')] {
+                assert_eq!(
+                    slice_chunker.chunks(count, delimiter).unwrap(),
+                    file_chunker.chunks(count, delimiter).unwrap()
+                );
+                assert_eq!(
+                    slice_chunker
+                        .chunk_iter(count, delimiter)
+                        .collect::<Vec<_>>(),
+                    file_chunker.chunks(count, delimiter).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn slice_chunker_through_trait() {
+        let chunker = SliceChunker::new(b"01\n23\n45\n67\n89");
+        let chunks = Chunker::chunks(&chunker, 2, Some('\n')).unwrap();
+        assert_eq!(chunks, vec![&b"01\n23\n45\n"[..], b"67\n89"]);
+    }
+}
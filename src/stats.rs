@@ -0,0 +1,17 @@
+/// Summary statistics about the chunks a file is divided into.
+///
+/// This is returned by [`FileChunker::chunk_stats`](crate::FileChunker::chunk_stats).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChunkStats {
+    /// The number of chunks.
+    pub count: usize,
+    /// The length of the smallest chunk, or zero if there are no chunks.
+    pub min_len: usize,
+    /// The length of the largest chunk, or zero if there are no chunks.
+    pub max_len: usize,
+    /// The mean chunk length, or zero if there are no chunks.
+    pub mean_len: f64,
+    /// Whether the last chunk ends with the delimiter. This is `false` if there are no chunks or
+    /// no delimiter was given.
+    pub last_ends_on_delimiter: bool,
+}
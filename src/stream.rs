@@ -0,0 +1,132 @@
+use crate::{ChunkerError, Result};
+use std::io::Read;
+
+// Reading in pieces of at least this size keeps the number of `read` calls low when `max_bytes`
+// is small.
+const MIN_READ_SIZE: usize = 64 * 1024;
+
+/// Read `reader` incrementally and call `f` with each chunk, where a chunk holds at least
+/// `max_bytes` bytes and ends with the first `delimiter` at or beyond that point. This produces
+/// the same chunks as [`FileChunker::chunks_by_size`](crate::FileChunker::chunks_by_size), but
+/// without mapping or buffering the whole input, so it suits inputs that are larger than memory
+/// or can't be memory-mapped.
+///
+/// Only a rolling buffer is held in memory, of about `max_bytes` plus the length of the longest
+/// record plus one read of at least 64 KiB. Each chunk borrows that buffer, so it must be copied
+/// if it's needed after `f` returns.
+///
+/// Returns [`ChunkerError::InvalidChunkSize`] if `max_bytes` is zero.
+pub fn stream_chunks<R, F>(reader: R, max_bytes: usize, delimiter: u8, f: F) -> Result<()>
+where
+    R: Read,
+    F: FnMut(&[u8]),
+{
+    stream_chunks_with_read_size(
+        reader,
+        max_bytes,
+        delimiter,
+        max_bytes.max(MIN_READ_SIZE),
+        f,
+    )
+}
+
+fn stream_chunks_with_read_size<R, F>(
+    mut reader: R,
+    max_bytes: usize,
+    delimiter: u8,
+    read_size: usize,
+    mut f: F,
+) -> Result<()>
+where
+    R: Read,
+    F: FnMut(&[u8]),
+{
+    if max_bytes == 0 {
+        return Err(ChunkerError::InvalidChunkSize);
+    }
+
+    let mut buffer = Vec::new();
+    // The index in `buffer` from which to look for the delimiter that ends the current chunk.
+    let mut search_from = max_bytes;
+    loop {
+        let read = reader
+            .by_ref()
+            .take(read_size as u64)
+            .read_to_end(&mut buffer)?;
+
+        let mut start = 0;
+        while let Some(position) = buffer
+            .get(search_from..)
+            .and_then(|rest| memchr::memchr(delimiter, rest))
+        {
+            let end = search_from + position + 1;
+            f(&buffer[start..end]);
+            start = end;
+            search_from = end.saturating_add(max_bytes);
+        }
+        // The rest of the buffer has no delimiter, so the next search can skip it. Without this, a
+        // record much longer than a read would be searched again from its start after each read.
+        search_from = search_from.max(buffer.len());
+        buffer.drain(..start);
+        search_from -= start;
+
+        if read == 0 {
+            break;
+        }
+    }
+    if !buffer.is_empty() {
+        f(&buffer);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FileChunker;
+    use std::fs::File;
+    use std::io::Write;
+
+    #[test]
+    fn stream_chunks_matches_chunks_by_size() {
+        let log = "01\n23\n45\n67\n89\nabcdefghij\nk\nlm";
+
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(log.as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let chunker = FileChunker::new(&file).unwrap();
+        for max_bytes in 1..=log.len() + 1 {
+            let expected = chunker.chunks_by_size(max_bytes, Some('\n')).unwrap();
+            for read_size in [1, 3, 7, log.len()] {
+                let mut chunks = Vec::new();
+                stream_chunks_with_read_size(
+                    log.as_bytes(),
+                    max_bytes,
+                    b'\n',
+                    read_size,
+                    |chunk| chunks.push(chunk.to_vec()),
+                )
+                .unwrap();
+                assert_eq!(
+                    chunks, expected,
+                    "max_bytes {} read_size {}",
+                    max_bytes, read_size
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stream_chunks_empty_and_invalid() {
+        let mut chunks = 0;
+        stream_chunks(std::io::empty(), 4, b'\n', |_| chunks += 1).unwrap();
+        assert_eq!(chunks, 0);
+
+        assert!(matches!(
+            stream_chunks(&b"01\n"[..], 0, b'\n', |_| {}),
+            Err(ChunkerError::InvalidChunkSize)
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::backing::Backing;
+use crate::{FileChunker, Result};
+use std::fs::File;
+use std::io::Read;
+
+impl FileChunker {
+    /// Create a new FileChunker by decompressing a zstd-compressed file into memory. The
+    /// chunking methods then operate on the decompressed data.
+    ///
+    /// Requires the `zstd` feature.
+    pub fn from_zstd(file: &File) -> Result<Self> {
+        let mut buffer = Vec::new();
+        zstd::stream::read::Decoder::new(file)?.read_to_end(&mut buffer)?;
+        Ok(Self::from_backing(Backing::Buffer(buffer), None))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ChunkerError, FileChunker};
+    use std::fs::File;
+    use std::io::{Seek, Write};
+
+    #[test]
+    fn from_zstd() {
+        let log = "01\n23\n45\n67\n89";
+
+        let file: File = tempfile::tempfile().unwrap();
+        let mut encoder = zstd::stream::write::Encoder::new(file, 0).unwrap();
+        encoder.write_all(log.as_bytes()).unwrap();
+        let mut file = encoder.finish().unwrap();
+        file.rewind().unwrap();
+
+        let chunker = FileChunker::from_zstd(&file).unwrap();
+        let chunks = chunker.chunks(2, Some('\n')).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(String::from_utf8_lossy(chunks[0]), "01\n23\n45\n");
+        assert_eq!(String::from_utf8_lossy(chunks[1]), "67\n89");
+    }
+
+    #[test]
+    fn from_zstd_invalid() {
+        let mut file: File = tempfile::tempfile().unwrap();
+        file.write_all(b"not zstd").unwrap();
+        file.rewind().unwrap();
+
+        assert!(matches!(
+            FileChunker::from_zstd(&file),
+            Err(ChunkerError::Io(_))
+        ));
+    }
+}